@@ -0,0 +1,173 @@
+#[cfg(feature = "alloc-stats")]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+
+/// Global allocator that counts allocations on top of the system allocator.
+/// Only installed when the `alloc-stats` feature is enabled.
+#[cfg(feature = "alloc-stats")]
+pub struct Counting;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Allocations made by one thread. Memory freed by a different thread than
+/// the one that allocated it is subtracted from the freeing thread's live
+/// bytes so those can go negative.
+#[derive(Copy, Clone)]
+struct Counters {
+    allocations: usize,
+    bytes: usize,
+    live: isize,
+    peak: isize,
+}
+
+thread_local! {
+    // Counted per thread so work left running in the background after a
+    // timeout is never charged to the next part.
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+/// Updates the counters of the current thread. Does nothing once the thread
+/// local storage is gone during thread exit.
+///
+/// * `update` - Changes the counters.
+#[cfg(feature = "alloc-stats")]
+fn update<F: FnOnce(&mut Counters)>(update: F) {
+    let _ = COUNTERS.try_with(|c| {
+        let mut counters = c.get();
+        update(&mut counters);
+        c.set(counters);
+    });
+}
+
+#[cfg(feature = "alloc-stats")]
+impl Counting {
+    fn allocated(size: usize) {
+        update(|c| {
+            c.allocations += 1;
+            c.bytes += size;
+            c.live += size as isize;
+            c.peak = c.peak.max(c.live);
+        });
+    }
+
+    fn freed(size: usize) {
+        update(|c| c.live -= size as isize);
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // Count a reallocation as freeing the old block and allocating a new one.
+            Self::freed(layout.size());
+            Self::allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Returns true if allocations are being counted.
+pub fn enabled() -> bool {
+    cfg!(feature = "alloc-stats")
+}
+
+/// Allocator counters of the current thread at some point in time.
+pub(crate) struct Checkpoint {
+    start: Counters,
+}
+
+impl Checkpoint {
+    /// Records the counters of the current thread and starts tracking a new
+    /// peak. Only allocations made by the same thread are counted so the
+    /// checkpoint should be created on the thread doing the work.
+    pub(crate) fn new() -> Self {
+        let mut start = COUNTERS.with(Cell::get);
+        start.peak = start.live;
+        COUNTERS.with(|c| c.set(start));
+        Self { start }
+    }
+
+    /// Returns the allocations made by the current thread since the checkpoint.
+    pub(crate) fn stats(&self) -> AllocStats {
+        let now = COUNTERS.with(Cell::get);
+        AllocStats {
+            allocations: now.allocations - self.start.allocations,
+            bytes: now.bytes - self.start.bytes,
+            peak: (now.peak - self.start.live).max(0) as usize,
+        }
+    }
+}
+
+/// Allocations made while running some code.
+#[derive(Copy, Clone)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub bytes: usize,
+    pub peak: usize,
+}
+
+/// Formats a byte count using binary units.
+///
+/// * `bytes` - The byte count.
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+impl fmt::Display for AllocStats {
+    /// Formats the value using the given formatter.
+    ///
+    /// * `f`     - Formatter
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
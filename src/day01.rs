@@ -1,39 +1,296 @@
-use itertools::multizip;
-use std::fs;
-
-fn read(input_file: &str) -> Vec<i64> {
-    let contents = fs::read_to_string(input_file).expect("Something went wrong reading the file");
-    contents
-        .lines()
-        .map(|s| {
-            s.parse()
-                .expect(format!("invalid non-numeric input '{s}'").as_str())
-        })
-        .collect()
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+/// Streams depths from the file one line at a time.
+fn read(input_file: &str) -> impl Iterator<Item = i64> {
+    let file = File::open(input_file).expect("Something went wrong reading the file");
+    BufReader::new(file).lines().map(|line| {
+        let s = line.expect("Something went wrong reading the file");
+        s.parse()
+            .unwrap_or_else(|_| panic!("invalid non-numeric input '{s}'"))
+    })
 }
 
-fn calc_increases(depths: Vec<i64>) -> i64 {
-    match depths.len() {
-        0 => 0,
-        _ => depths
-            .iter()
-            .zip(depths.iter().skip(1))
-            .fold(0, |a, (d0, d1)| if d1 > d0 { a + 1 } else { a }),
+/// Counts how often the sum of a sliding window of depths increases.
+///
+/// Consecutive windows share all but one depth so the sum only increases when
+/// the depth entering the window is larger than the one leaving it. Only the
+/// last `window` depths are kept so memory use does not grow with the input.
+///
+/// Works on any source of depths, e.g. a file being streamed or readings
+/// arriving from a sensor. Panics if `window` is zero.
+///
+/// * `depths` - The depths.
+/// * `window` - Number of depths summed in each window.
+pub fn calc_increases<I>(depths: I, window: usize) -> i64
+where
+    I: IntoIterator<Item = i64>,
+{
+    assert!(window > 0, "window must contain at least one depth");
+
+    let mut recent: VecDeque<i64> = VecDeque::with_capacity(window);
+    let mut increases = 0;
+    for depth in depths {
+        if recent.len() == window {
+            let leaving = recent.pop_front().unwrap();
+            if depth > leaving {
+                increases += 1;
+            }
+        }
+        recent.push_back(depth);
     }
+    increases
+}
+
+pub fn part1(input_file: &str) -> String {
+    calc_increases(read(input_file), 1).to_string()
+}
+
+pub fn part2(input_file: &str) -> String {
+    calc_increases(read(input_file), 3).to_string()
+}
+
+/// Readings in the moving window unless given on the command line.
+pub const DEFAULT_WINDOW: usize = 10;
+
+/// Standard deviations for outliers unless given on the command line.
+pub const DEFAULT_THRESHOLD: f64 = 3.0;
+
+/// A stretch of consecutive readings that keep going the same way.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Run {
+    pub start: usize,
+    pub len: usize,
 }
 
-pub fn part1(input_file: &str) {
-    let depths = read(input_file);
-    let n = calc_increases(depths);
-    println!("day 01: part 1 = {}", n);
+impl fmt::Display for Run {
+    /// Formats the value using the given formatter.
+    ///
+    /// * `f`     - Formatter
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.len {
+            0 => write!(f, "none"),
+            n => write!(
+                f,
+                "{} readings from #{} to #{}",
+                n,
+                self.start,
+                self.start + n - 1
+            ),
+        }
+    }
+}
+
+/// Finds the longest run while readings arrive one at a time.
+#[derive(Default)]
+struct RunTracker {
+    current: Run,
+    longest: Run,
+}
+
+impl RunTracker {
+    /// Records the step from the previous reading to the reading at `i`.
+    /// Ties go to the earliest run.
+    ///
+    /// * `i`       - Index of the new reading.
+    /// * `extends` - Whether the step keeps going the same way as the run.
+    fn step(&mut self, i: usize, extends: bool) {
+        self.current = match (extends, self.current.len) {
+            (false, _) => Run::default(),
+            (true, 0) => Run {
+                start: i - 1,
+                len: 2,
+            },
+            (true, len) => Run {
+                start: self.current.start,
+                len: len + 1,
+            },
+        };
+        if self.current.len > self.longest.len {
+            self.longest = self.current;
+        }
+    }
+}
+
+/// Smallest and largest of a series of values.
+#[derive(Copy, Clone, Debug)]
+pub struct Range {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Range {
+    /// Widens the range to include the value.
+    ///
+    /// * `range` - The range so far or `None` if there are no values yet.
+    /// * `v`     - The value.
+    fn include(range: Option<Self>, v: f64) -> Option<Self> {
+        Some(match range {
+            None => Self { min: v, max: v },
+            Some(r) => Self {
+                min: r.min.min(v),
+                max: r.max.max(v),
+            },
+        })
+    }
 }
 
-pub fn part2(input_file: &str) {
-    let depths = read(input_file);
-    let sliding_depths: Vec<_> =
-        multizip((depths.iter(), depths.iter().skip(1), depths.iter().skip(2)))
-            .map(|(d0, d1, d2)| d0 + d1 + d2)
-            .collect();
-    let n = calc_increases(sliding_depths);
-    println!("day 01: part 2 = {}", n);
+/// Summary of a depth profile. Readings are numbered from 0 in input order.
+/// Moving statistics cover each full window of readings. Only the last
+/// `window` readings are kept while analysing so memory use does not grow
+/// with the input.
+pub struct DepthReport {
+    pub readings: usize,
+    pub window: usize,
+    pub threshold: f64,
+    pub longest_increasing: Run,
+    pub longest_decreasing: Run,
+    pub largest_jump: Option<(usize, i64)>,
+    pub moving_average: Option<Range>,
+    pub moving_median: Option<Range>,
+    pub outliers: Vec<(usize, i64)>,
+}
+
+impl DepthReport {
+    /// Analyses the depths. Readings more than `threshold` standard
+    /// deviations away from the mean of the preceding window are outliers.
+    ///
+    /// * `depths`    - The depths.
+    /// * `window`    - Number of readings in the moving window.
+    /// * `threshold` - Standard deviations from the local mean for outliers.
+    pub fn new<I>(depths: I, window: usize, threshold: f64) -> Self
+    where
+        I: IntoIterator<Item = i64>,
+    {
+        assert!(window > 0, "window must contain at least one depth");
+
+        let mut report = Self {
+            readings: 0,
+            window,
+            threshold,
+            longest_increasing: Run::default(),
+            longest_decreasing: Run::default(),
+            largest_jump: None,
+            moving_average: None,
+            moving_median: None,
+            outliers: vec![],
+        };
+
+        let mut increasing = RunTracker::default();
+        let mut decreasing = RunTracker::default();
+        let mut recent: VecDeque<i64> = VecDeque::with_capacity(window + 1);
+        let mut sorted: Vec<i64> = Vec::with_capacity(window);
+
+        for (i, depth) in depths.into_iter().enumerate() {
+            report.readings += 1;
+
+            if let Some(&previous) = recent.back() {
+                increasing.step(i, depth > previous);
+                decreasing.step(i, depth < previous);
+
+                let jump = depth - previous;
+                if report
+                    .largest_jump
+                    .is_none_or(|(_, j)| jump.abs() >= j.abs())
+                {
+                    report.largest_jump = Some((i, jump));
+                }
+            }
+
+            // Compare each reading with the window just before it.
+            if recent.len() == window {
+                let mean = Self::mean(&recent);
+                let variance = recent
+                    .iter()
+                    .map(|&d| (d as f64 - mean).powi(2))
+                    .sum::<f64>()
+                    / window as f64;
+                let deviation = (depth as f64 - mean).abs();
+                if variance > 0.0 && deviation > threshold * variance.sqrt() {
+                    report.outliers.push((i, depth));
+                }
+            }
+
+            recent.push_back(depth);
+            if recent.len() > window {
+                recent.pop_front();
+            }
+            if recent.len() < window {
+                continue;
+            }
+
+            report.moving_average = Range::include(report.moving_average, Self::mean(&recent));
+
+            sorted.clear();
+            sorted.extend(recent.iter());
+            sorted.sort_unstable();
+            let mid = window / 2;
+            let median = if window % 2 == 1 {
+                sorted[mid] as f64
+            } else {
+                (sorted[mid - 1] + sorted[mid]) as f64 / 2.0
+            };
+            report.moving_median = Range::include(report.moving_median, median);
+        }
+
+        report.longest_increasing = increasing.longest;
+        report.longest_decreasing = decreasing.longest;
+        report
+    }
+
+    /// Reads the depths from a file and analyses them.
+    ///
+    /// * `input_file` - The input file.
+    /// * `window`     - Number of readings in the moving window.
+    /// * `threshold`  - Standard deviations from the local mean for outliers.
+    pub fn read(input_file: &str, window: usize, threshold: f64) -> Self {
+        Self::new(read(input_file), window, threshold)
+    }
+
+    fn mean(depths: &VecDeque<i64>) -> f64 {
+        depths.iter().sum::<i64>() as f64 / depths.len() as f64
+    }
+}
+
+impl fmt::Display for DepthReport {
+    /// Formats the value using the given formatter.
+    ///
+    /// * `f`     - Formatter
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "readings: {}", self.readings)?;
+        writeln!(f, "longest increasing run: {}", self.longest_increasing)?;
+        writeln!(f, "longest decreasing run: {}", self.longest_decreasing)?;
+        match self.largest_jump {
+            Some((i, jump)) => writeln!(f, "largest jump: {:+} at #{}", jump, i)?,
+            None => writeln!(f, "largest jump: none")?,
+        }
+
+        let stats = [
+            ("average", self.moving_average),
+            ("median", self.moving_median),
+        ];
+        for (name, range) in stats {
+            match range {
+                Some(Range { min, max }) => writeln!(
+                    f,
+                    "moving {} over {} readings: {:.2} to {:.2}",
+                    name, self.window, min, max
+                )?,
+                None => writeln!(f, "moving {} over {} readings: none", name, self.window)?,
+            }
+        }
+
+        write!(
+            f,
+            "outliers beyond {} standard deviations: {}",
+            self.threshold,
+            self.outliers.len()
+        )?;
+        for (i, depth) in self.outliers.iter() {
+            write!(f, "\n  #{}: {}", i, depth)?;
+        }
+        Ok(())
+    }
 }
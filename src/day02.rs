@@ -1,54 +1,297 @@
+use std::fmt;
 use std::fs;
 
-fn read(input_file: &str) -> Vec<(String, i64)> {
+use crate::repl::Repl;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Command {
+    Forward(i64),
+    Back(i64),
+    Down(i64),
+    Up(i64),
+    Reset,
+}
+
+impl Command {
+    /// Parses a command such as `forward 5` or `reset`.
+    ///
+    /// * `s` - The command.
+    fn parse(s: &str) -> Result<Self, String> {
+        let tokens: Vec<&str> = s.split_whitespace().collect();
+        let value = |v: &str| {
+            v.parse::<i64>()
+                .map_err(|_| format!("Invalid command value '{}'", v))
+        };
+
+        match tokens[..] {
+            ["forward", v] => Ok(Self::Forward(value(v)?)),
+            ["back", v] => Ok(Self::Back(value(v)?)),
+            ["down", v] => Ok(Self::Down(value(v)?)),
+            ["up", v] => Ok(Self::Up(value(v)?)),
+            ["reset"] => Ok(Self::Reset),
+            [c, ..] => Err(format!("Invalid command '{}'", c)),
+            [] => Err("Missing command".to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Command {
+    /// Formats the value using the given formatter.
+    ///
+    /// * `f`     - Formatter
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Forward(v) => write!(f, "forward {}", v),
+            Self::Back(v) => write!(f, "back {}", v),
+            Self::Down(v) => write!(f, "down {}", v),
+            Self::Up(v) => write!(f, "up {}", v),
+            Self::Reset => write!(f, "reset"),
+        }
+    }
+}
+
+fn read(input_file: &str) -> Vec<Command> {
     let contents = fs::read_to_string(input_file).expect("Something went wrong reading the file");
     contents
         .lines()
-        .map(|s| {
-            let c: Vec<&str> = s.split(" ").collect();
-            let command = c[0].to_string();
-            let value: i64 = c[1]
-                .parse()
-                .expect(format!("Invalid command value '{}'", c[1]).as_str());
-            (command, value)
-        })
+        .enumerate()
+        .map(|(i, s)| Command::parse(s).unwrap_or_else(|e| panic!("line {}: {}", i + 1, e)))
         .collect()
 }
 
-pub fn part1(input_file: &str) {
-    let commands = read(input_file);
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+struct Position {
+    x: i64,
+    depth: i64,
+    aim: i64,
+}
 
-    let mut x: i64 = 0;
-    let mut y: i64 = 0;
+/// Every command executed along with the position right after it. Commands
+/// are numbered from 1.
+#[derive(Default)]
+struct Course {
+    steps: Vec<(Command, Position)>,
+}
 
-    for (command, value) in commands {
-        match &command[..] {
-            "forward" => x += value,
-            "down" => y += value,
-            "up" => y -= value,
-            c => panic!("Invalid command {}", c),
+impl Course {
+    fn to_csv(&self) -> String {
+        let mut csv = String::from("step,command,x,depth,aim\n");
+        for (i, (command, p)) in self.steps.iter().enumerate() {
+            csv += &format!("{},{},{},{},{}\n", i + 1, command, p.x, p.depth, p.aim);
         }
+        csv
+    }
+
+    /// Returns the first command that reached the maximum depth and that
+    /// depth or `None` if no commands were executed.
+    fn max_depth(&self) -> Option<(usize, i64)> {
+        self.steps
+            .iter()
+            .enumerate()
+            .map(|(i, (_, p))| (i + 1, p.depth))
+            .rev()
+            .max_by_key(|&(_, depth)| depth)
+    }
+
+    /// Returns the first command after which the depth exceeds the limit.
+    ///
+    /// * `limit` - The depth limit.
+    fn first_deeper_than(&self, limit: i64) -> Option<usize> {
+        self.steps
+            .iter()
+            .position(|(_, p)| p.depth > limit)
+            .map(|i| i + 1)
     }
-    println!("day 02: part 1 = {}", x * y);
 }
 
-pub fn part2(input_file: &str) {
-    let commands = read(input_file);
+impl fmt::Display for Course {
+    /// Formats the value using the given formatter.
+    ///
+    /// * `f`     - Formatter
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>5}  {:<12} {:>8} {:>8} {:>8}",
+            "step", "command", "x", "depth", "aim"
+        )?;
+        for (i, (command, p)) in self.steps.iter().enumerate() {
+            let command = command.to_string();
+            write!(
+                f,
+                "\n{:>5}  {:<12} {:>8} {:>8} {:>8}",
+                i + 1,
+                command,
+                p.x,
+                p.depth,
+                p.aim
+            )?;
+        }
+        Ok(())
+    }
+}
 
-    let mut x: i64 = 0;
-    let mut y: i64 = 0;
-    let mut aim: i64 = 0;
+/// Navigation rules that decide how each command changes the position.
+trait Movement {
+    /// Applies a movement command. `Reset` is handled by the submarine.
+    ///
+    /// * `position` - The position to update.
+    /// * `command`  - The command.
+    fn apply(&self, position: &mut Position, command: Command);
+}
+
+/// Part 1 rules where `down` and `up` change the depth directly.
+struct Direct;
 
-    for (command, value) in commands {
-        match &command[..] {
-            "forward" => {
-                x += value;
-                y += aim * value;
+impl Movement for Direct {
+    fn apply(&self, position: &mut Position, command: Command) {
+        match command {
+            Command::Forward(v) => position.x += v,
+            Command::Back(v) => position.x -= v,
+            Command::Down(v) => position.depth += v,
+            Command::Up(v) => position.depth -= v,
+            Command::Reset => {}
+        }
+    }
+}
+
+/// Part 2 rules where `down` and `up` change the aim and moving along the
+/// course changes the depth by the aim.
+struct Aim;
+
+impl Movement for Aim {
+    fn apply(&self, position: &mut Position, command: Command) {
+        match command {
+            Command::Forward(v) => {
+                position.x += v;
+                position.depth += position.aim * v;
+            }
+            Command::Back(v) => {
+                position.x -= v;
+                position.depth -= position.aim * v;
             }
-            "down" => aim += value,
-            "up" => aim -= value,
-            c => panic!("Invalid command {}", c),
+            Command::Down(v) => position.aim += v,
+            Command::Up(v) => position.aim -= v,
+            Command::Reset => {}
         }
     }
-    println!("day 02: part 2 = {}", x * y);
+}
+
+struct Submarine<M: Movement> {
+    model: M,
+    position: Position,
+    course: Course,
+}
+
+impl<M: Movement> Submarine<M> {
+    fn new(model: M) -> Self {
+        Self {
+            model,
+            position: Position::default(),
+            course: Course::default(),
+        }
+    }
+
+    fn execute(&mut self, command: Command) {
+        match command {
+            Command::Reset => self.position = Position::default(),
+            c => self.model.apply(&mut self.position, c),
+        }
+        self.course.steps.push((command, self.position));
+    }
+
+    fn run(&mut self, commands: &[Command]) -> Position {
+        for &command in commands {
+            self.execute(command);
+        }
+        self.position
+    }
+}
+
+/// Replays the course taken with each movement model.
+pub struct CourseRepl {
+    courses: Vec<(&'static str, Course)>,
+    model: usize,
+}
+
+impl CourseRepl {
+    pub fn new(input_file: &str) -> Self {
+        let commands = read(input_file);
+
+        let mut direct = Submarine::new(Direct);
+        direct.run(&commands);
+        let mut aim = Submarine::new(Aim);
+        aim.run(&commands);
+
+        Self {
+            courses: vec![("direct", direct.course), ("aim", aim.course)],
+            model: 0,
+        }
+    }
+
+    fn course(&self) -> &Course {
+        &self.courses[self.model].1
+    }
+}
+
+impl Repl for CourseRepl {
+    fn help(&self) -> Vec<&'static str> {
+        vec![
+            "model [direct|aim]  show or change the movement model",
+            "show                show the position after every command",
+            "csv <file>          export the course as CSV",
+            "max                 find the maximum depth reached",
+            "exceeds <depth>     find the first command that goes deeper",
+        ]
+    }
+
+    fn eval(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match (command, args) {
+            ("model", []) => Ok(self.courses[self.model].0.to_string()),
+            ("model", [name]) => {
+                self.model = self
+                    .courses
+                    .iter()
+                    .position(|(n, _)| n == name)
+                    .ok_or(format!("unknown model '{}'", name))?;
+                Ok(format!("using {} model", name))
+            }
+            ("show", []) => Ok(self.course().to_string()),
+            ("csv", [file]) => {
+                fs::write(file, self.course().to_csv()).map_err(|e| e.to_string())?;
+                Ok(format!(
+                    "wrote {} commands to {}",
+                    self.course().steps.len(),
+                    file
+                ))
+            }
+            ("max", []) => match self.course().max_depth() {
+                Some((i, depth)) => Ok(format!("depth {} after command {}", depth, i)),
+                None => Err("no commands in input".to_string()),
+            },
+            ("exceeds", [limit]) => {
+                let limit = limit
+                    .parse::<i64>()
+                    .map_err(|_| format!("invalid depth '{}'", limit))?;
+                match self.course().first_deeper_than(limit) {
+                    Some(i) => Ok(format!("command {}: {}", i, self.course().steps[i - 1].0)),
+                    None => Ok(format!("never deeper than {}", limit)),
+                }
+            }
+            _ => Err(format!("unknown command '{}'", command)),
+        }
+    }
+}
+
+fn navigate<M: Movement>(input_file: &str, model: M) -> String {
+    let commands = read(input_file);
+    let position = Submarine::new(model).run(&commands);
+    (position.x * position.depth).to_string()
+}
+
+pub fn part1(input_file: &str) -> String {
+    navigate(input_file, Direct)
+}
+
+pub fn part2(input_file: &str) -> String {
+    navigate(input_file, Aim)
 }
@@ -1,37 +1,49 @@
+use std::fmt;
 use std::fs;
 
-fn read(input_file: &str) -> (Vec<u16>, usize) {
+use crate::repl::Repl;
+
+/// Widest report that fits in the diagnostic values.
+const MAX_BITS: usize = u64::BITS as usize;
+
+fn read(input_file: &str) -> (Vec<u64>, usize) {
     let contents = fs::read_to_string(input_file).expect("Something went wrong reading the file");
     let contents: Vec<&str> = contents.lines().collect();
     let n = contents[0].len();
+    assert!(
+        n > 0 && n <= MAX_BITS,
+        "expecting between 1 and {MAX_BITS} bits per line, found {n}"
+    );
 
     let contents = contents
         .iter()
-        .map(|s| u16::from_str_radix(s, 2).expect(&format!("invalid non-numeric input {s}")))
+        .map(|s| {
+            assert!(s.len() == n, "expecting {n} bits per line in {s}");
+            u64::from_str_radix(s, 2).unwrap_or_else(|_| panic!("invalid non-numeric input {s}"))
+        })
         .collect();
     (contents, n)
 }
 
-fn calc_sum_all_bits(diagnostics: &Vec<u16>, num_bits: usize) -> Vec<usize> {
+fn calc_sum_all_bits(diagnostics: &Vec<u64>, num_bits: usize) -> Vec<usize> {
     // sum_bits[] is least to most significant bit
     let mut sum_bits = vec![0_usize; num_bits];
     for bits in diagnostics {
         for bit in 0..num_bits {
-            let mask = 1 << bit;
-            sum_bits[bit] += (*bits as usize & mask) >> bit;
+            sum_bits[bit] += ((*bits >> bit) & 1) as usize;
         }
     }
     sum_bits
 }
 
-pub fn part1(input_file: &str) {
+pub fn part1(input_file: &str) -> String {
     let (diagnostics, num_bits) = read(input_file);
     let n = diagnostics.len();
 
     let sum_bits = calc_sum_all_bits(&diagnostics, num_bits);
 
-    let mut gamma = 0;
-    let mut epsilon = 0;
+    let mut gamma = 0_u64;
+    let mut epsilon = 0_u64;
     for bit in 0..num_bits {
         let mask = 1 << bit;
         if sum_bits[bit] >= n - sum_bits[bit] {
@@ -41,36 +53,106 @@ pub fn part1(input_file: &str) {
         }
     }
 
-    println!(
-        "day 03: part 1 = {} * {} = {}",
-        gamma,
-        epsilon,
-        gamma * epsilon
-    );
+    (gamma as u128 * epsilon as u128).to_string()
 }
 
-fn calc_sum_bits(diagnostics: &Vec<u16>, bit: usize) -> usize {
+#[cfg(test)]
+fn calc_sum_bits(diagnostics: &Vec<u64>, bit: usize) -> usize {
     // bit 0 is least significant
     let mut sum_bits = 0_usize;
     for bits in diagnostics {
-        let mask = 1 << bit;
-        sum_bits += (*bits as usize & mask) >> bit;
+        sum_bits += ((*bits >> bit) & 1) as usize;
     }
     sum_bits
 }
 
-fn is_bit_set(bits: &u16, bit: usize) -> bool {
-    (*bits as usize & (1 << bit)) >> bit == 1
+#[cfg(test)]
+fn is_bit_set(bits: &u64, bit: usize) -> bool {
+    (*bits >> bit) & 1 == 1
+}
+
+/// Which bit value is kept when there are as many 0s as 1s.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Tie {
+    Zero,
+    One,
+}
+
+/// Rule that decides which values are kept at each bit of a rating search.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum BitCriteria {
+    /// Keep the values with the more common bit value.
+    MostCommon(Tie),
+
+    /// Keep the values with the less common bit value.
+    LeastCommon(Tie),
+
+    /// Keep the values with a 1 if at least this percentage of the remaining
+    /// values have a 1.
+    Majority(usize),
+}
+
+impl BitCriteria {
+    const OXYGEN_GENERATOR: Self = Self::MostCommon(Tie::One);
+    const CO2_SCRUBBER: Self = Self::LeastCommon(Tie::Zero);
+
+    /// Parses a policy name: `most`, `least`, `most:<tie>`, `least:<tie>` or
+    /// `majority:<percent>`. Ties go to 1 for `most` and 0 for `least`.
+    ///
+    /// * `s` - The policy name.
+    fn parse(s: &str) -> Result<Self, String> {
+        let tie = |t: &str| match t {
+            "0" => Ok(Tie::Zero),
+            "1" => Ok(Tie::One),
+            _ => Err(format!("invalid tie '{}'. expecting 0 or 1", t)),
+        };
+
+        match s.split_once(':') {
+            None if s == "most" => Ok(Self::OXYGEN_GENERATOR),
+            None if s == "least" => Ok(Self::CO2_SCRUBBER),
+            Some(("most", t)) => Ok(Self::MostCommon(tie(t)?)),
+            Some(("least", t)) => Ok(Self::LeastCommon(tie(t)?)),
+            Some(("majority", p)) => match p.parse::<usize>() {
+                Ok(percent) if percent <= 100 => Ok(Self::Majority(percent)),
+                _ => Err(format!("invalid percentage '{}'", p)),
+            },
+            _ => Err(format!("unknown bit criteria '{}'", s)),
+        }
+    }
+
+    /// Returns true to keep the values with a 1 in the current bit.
+    ///
+    /// * `ones` - Number of remaining values with a 1.
+    /// * `n`    - Number of remaining values.
+    fn keep_ones(&self, ones: usize, n: usize) -> bool {
+        let zeros = n - ones;
+        match *self {
+            Self::MostCommon(tie) => ones > zeros || (ones == zeros && tie == Tie::One),
+            Self::LeastCommon(tie) => ones < zeros || (ones == zeros && tie == Tie::One),
+            Self::Majority(percent) => ones * 100 >= percent * n,
+        }
+    }
 }
 
-fn calc_rating<C>(diagnostics: &Vec<u16>, num_bits: usize, bit_criteria: C) -> Vec<u16>
-where
-    C: Fn(usize, usize) -> bool,
-{
-    // bit_criteria(s, n) should return true if s out of n values are most commonly 1 OR
-    // least commonly 1.
+impl fmt::Display for BitCriteria {
+    /// Formats the value using the given formatter.
+    ///
+    /// * `f`     - Formatter
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tie = |t: &Tie| if *t == Tie::One { 1 } else { 0 };
+        match self {
+            Self::MostCommon(t) => write!(f, "most:{}", tie(t)),
+            Self::LeastCommon(t) => write!(f, "least:{}", tie(t)),
+            Self::Majority(percent) => write!(f, "majority:{}", percent),
+        }
+    }
+}
 
-    let mut v: Vec<u16> = diagnostics.clone();
+/// Reference implementation of the rating search. Re-filters the remaining
+/// values for every bit. Only used to test `Trie::rating`.
+#[cfg(test)]
+fn calc_rating(diagnostics: &Vec<u64>, num_bits: usize, bit_criteria: BitCriteria) -> Vec<u64> {
+    let mut v: Vec<u64> = diagnostics.clone();
     for bit in (0..num_bits).rev() {
         let n = v.len();
         if n == 1 {
@@ -78,7 +160,7 @@ where
         }
 
         let sum_bits = calc_sum_bits(&v, bit);
-        if bit_criteria(sum_bits, n) {
+        if bit_criteria.keep_ones(sum_bits, n) {
             v = v
                 .iter()
                 .filter(|&bits| is_bit_set(bits, bit))
@@ -95,23 +177,342 @@ where
     v
 }
 
-pub fn part2(input_file: &str) {
+#[derive(Default)]
+struct TrieNode {
+    children: [Option<usize>; 2],
+
+    // Number of values that pass through this node.
+    count: usize,
+}
+
+/// Binary trie of the diagnostic values starting from the most significant
+/// bit. The counts at each node give the number of remaining values with a 0
+/// or 1 in the next bit so a rating is found by walking a single path.
+struct Trie {
+    nodes: Vec<TrieNode>,
+    num_bits: usize,
+}
+
+impl Trie {
+    fn new(diagnostics: &[u64], num_bits: usize) -> Self {
+        let mut trie = Self {
+            nodes: vec![TrieNode::default()],
+            num_bits,
+        };
+        for &bits in diagnostics {
+            trie.insert(bits);
+        }
+        trie
+    }
+
+    fn insert(&mut self, bits: u64) {
+        let mut node = 0;
+        self.nodes[node].count += 1;
+        for bit in (0..self.num_bits).rev() {
+            let b = ((bits >> bit) & 1) as usize;
+            node = match self.nodes[node].children[b] {
+                Some(child) => child,
+                None => {
+                    self.nodes.push(TrieNode::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children[b] = Some(child);
+                    child
+                }
+            };
+            self.nodes[node].count += 1;
+        }
+    }
+
+    fn count(&self, child: Option<usize>) -> usize {
+        child.map_or(0, |c| self.nodes[c].count)
+    }
+
+    /// Returns the next bit value on the rating path from a node.
+    ///
+    /// * `node`         - The node.
+    /// * `bit_criteria` - The bit criteria.
+    fn choose(&self, node: usize, bit_criteria: BitCriteria) -> usize {
+        let TrieNode { children, count } = self.nodes[node];
+
+        // Once a single value is left follow its path to the end.
+        if count == 1 {
+            usize::from(children[1].is_some())
+        } else {
+            usize::from(bit_criteria.keep_ones(self.count(children[1]), count))
+        }
+    }
+
+    /// Finds the rating with the same rules as `calc_rating`. Returns `None`
+    /// if the criteria eliminate every value.
+    ///
+    /// * `bit_criteria` - The bit criteria.
+    fn rating(&self, bit_criteria: BitCriteria) -> Option<u64> {
+        let mut node = 0;
+        let mut rating = 0;
+        for bit in (0..self.num_bits).rev() {
+            let b = self.choose(node, bit_criteria);
+            node = self.nodes[node].children[b]?;
+            rating |= (b as u64) << bit;
+        }
+        Some(rating)
+    }
+
+    /// Returns every value below a node, repeating duplicates.
+    ///
+    /// * `node`   - The node.
+    /// * `prefix` - The bits on the path to the node.
+    /// * `bit`    - Number of bits below the node.
+    fn values(&self, node: usize, prefix: u64, bit: usize) -> Vec<u64> {
+        if bit == 0 {
+            return vec![prefix; self.nodes[node].count];
+        }
+        let children = self.nodes[node].children;
+        (0..2)
+            .filter_map(|b| children[b].map(|c| (b as u64, c)))
+            .flat_map(|(b, c)| self.values(c, prefix | (b << (bit - 1)), bit - 1))
+            .collect()
+    }
+
+    /// Finds the rating like `rating` and records the values eliminated at
+    /// each bit.
+    ///
+    /// * `bit_criteria` - The bit criteria.
+    fn explain(&self, bit_criteria: BitCriteria) -> RatingTrace {
+        let mut trace = RatingTrace {
+            bit_criteria,
+            num_bits: self.num_bits,
+            eliminations: vec![],
+            rating: None,
+        };
+
+        let mut node = 0;
+        let mut rating = 0;
+        for bit in (0..self.num_bits).rev() {
+            let b = self.choose(node, bit_criteria);
+            let children = self.nodes[node].children;
+
+            if let Some(other) = children[1 - b] {
+                let prefix = rating | (((1 - b) as u64) << bit);
+                trace.eliminations.push(Elimination {
+                    position: self.num_bits - bit,
+                    kept: b,
+                    eliminated: self.values(other, prefix, bit),
+                });
+            }
+
+            match children[b] {
+                Some(child) => node = child,
+                None => return trace,
+            }
+            rating |= (b as u64) << bit;
+        }
+
+        trace.rating = Some(rating);
+        trace
+    }
+}
+
+/// Values removed at one bit of a rating search.
+struct Elimination {
+    // Bit position counting from 1 at the most significant bit.
+    position: usize,
+
+    // The bit value of the values that were kept.
+    kept: usize,
+
+    eliminated: Vec<u64>,
+}
+
+/// Explains how a rating was selected.
+struct RatingTrace {
+    bit_criteria: BitCriteria,
+    num_bits: usize,
+    eliminations: Vec<Elimination>,
+    rating: Option<u64>,
+}
+
+impl fmt::Display for RatingTrace {
+    /// Formats the value using the given formatter.
+    ///
+    /// * `f`     - Formatter
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let w = self.num_bits;
+        write!(f, "criteria {}", self.bit_criteria)?;
+        for e in self.eliminations.iter() {
+            let eliminated: Vec<String> = e
+                .eliminated
+                .iter()
+                .map(|v| format!("{:0w$b}", v, w = w))
+                .collect();
+            write!(
+                f,
+                "\nbit {}: kept {}s, eliminated {}: {}",
+                e.position,
+                e.kept,
+                eliminated.len(),
+                eliminated.join(", ")
+            )?;
+        }
+        match self.rating {
+            Some(rating) => write!(f, "\nrating {:0w$b} ({})", rating, rating, w = w),
+            None => write!(f, "\nno rating: every value was eliminated"),
+        }
+    }
+}
+
+/// Multiplies the two ratings or returns `None` if either can't be found.
+///
+/// * `trie`   - The diagnostics.
+/// * `oxygen` - Bit criteria for the oxygen generator rating.
+/// * `co2`    - Bit criteria for the CO2 scrubber rating.
+fn life_support_rating(trie: &Trie, oxygen: BitCriteria, co2: BitCriteria) -> Option<u128> {
+    let o2 = trie.rating(oxygen)?;
+    let co2 = trie.rating(co2)?;
+    Some(o2 as u128 * co2 as u128)
+}
+
+pub fn part2(input_file: &str) -> String {
     let (diagnostics, num_bits) = read(input_file);
+    let trie = Trie::new(&diagnostics, num_bits);
 
-    let o2 = calc_rating(&diagnostics, num_bits, |sum_bits, n| {
-        // Most common: more than half the values are 1.
-        sum_bits >= n - sum_bits
-    });
-
-    let co2 = calc_rating(&diagnostics, num_bits, |sum_bits, n| {
-        // Least common: less than half the values are 1.
-        sum_bits < n - sum_bits
-    });
-
-    println!(
-        "day 03: part 2 = {} * {} = {}",
-        o2[0],
-        co2[0],
-        o2[0] as u32 * co2[0] as u32
-    );
+    life_support_rating(
+        &trie,
+        BitCriteria::OXYGEN_GENERATOR,
+        BitCriteria::CO2_SCRUBBER,
+    )
+    .expect("every value was eliminated from a rating")
+    .to_string()
+}
+
+/// Shows how ratings are selected under different bit criteria.
+pub struct DiagnosticsRepl {
+    trie: Trie,
+}
+
+impl DiagnosticsRepl {
+    pub fn new(input_file: &str) -> Self {
+        let (diagnostics, num_bits) = read(input_file);
+        Self {
+            trie: Trie::new(&diagnostics, num_bits),
+        }
+    }
+}
+
+impl Repl for DiagnosticsRepl {
+    fn help(&self) -> Vec<&'static str> {
+        vec![
+            "rating <criteria>      explain which values each bit eliminates",
+            "life [<o2> <co2>]      multiply the ratings (default most least)",
+            "criteria: most, least, most:<tie>, least:<tie>, majority:<percent>",
+        ]
+    }
+
+    fn eval(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match (command, args) {
+            ("rating", [c]) => Ok(self.trie.explain(BitCriteria::parse(c)?).to_string()),
+            ("life", []) => self.eval(command, &["most", "least"]),
+            ("life", [o2, co2]) => {
+                let o2 = BitCriteria::parse(o2)?;
+                let co2 = BitCriteria::parse(co2)?;
+                life_support_rating(&self.trie, o2, co2)
+                    .map(|r| r.to_string())
+                    .ok_or("every value was eliminated from a rating".to_string())
+            }
+            _ => Err(format!("unknown command '{}'", command)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every bit criteria including each majority percentage at a step of 5.
+    fn all_bit_criteria() -> Vec<BitCriteria> {
+        let mut all = vec![];
+        for tie in [Tie::Zero, Tie::One] {
+            all.push(BitCriteria::MostCommon(tie));
+            all.push(BitCriteria::LeastCommon(tie));
+        }
+        all.extend((0..=100).step_by(5).map(BitCriteria::Majority));
+        all
+    }
+
+    /// Deterministic pseudo-random values so the inputs don't change between runs.
+    fn random_values(seed: u64, n: usize) -> Vec<u64> {
+        let mut x = seed;
+        (0..n)
+            .map(|_| {
+                // xorshift64
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                x
+            })
+            .collect()
+    }
+
+    /// Compares the trie with the reference implementation for every bit
+    /// criteria.
+    fn check(diagnostics: &[u64], num_bits: usize) {
+        let diagnostics = diagnostics.to_vec();
+        let trie = Trie::new(&diagnostics, num_bits);
+        for bit_criteria in all_bit_criteria() {
+            assert_eq!(
+                trie.rating(bit_criteria),
+                calc_rating(&diagnostics, num_bits, bit_criteria)
+                    .first()
+                    .copied(),
+                "{} bits with criteria {}",
+                num_bits,
+                bit_criteria
+            );
+        }
+    }
+
+    #[test]
+    fn example() {
+        let (diagnostics, num_bits) = read("./inputs/day03/0.txt");
+        check(&diagnostics, num_bits);
+        assert_eq!(part2("./inputs/day03/0.txt"), "230");
+    }
+
+    #[test]
+    fn wide() {
+        for seed in 1..=20 {
+            check(&random_values(seed, 200), MAX_BITS);
+        }
+    }
+
+    #[test]
+    fn duplicates() {
+        // Few distinct values repeated many times.
+        for seed in 1..=20 {
+            let values: Vec<u64> = random_values(seed, 300)
+                .iter()
+                .map(|v| [0b1011, 0b0110, 0b1110, 0b0001][(v % 4) as usize])
+                .collect();
+            check(&values, 4);
+            check(&[values[0]; 10], 4);
+        }
+    }
+
+    #[test]
+    fn ties() {
+        // Every value up to a width appears equally often so every bit ties
+        // until the values are narrowed down.
+        for num_bits in 1..=8 {
+            let values: Vec<u64> = (0..1 << num_bits).chain(0..1 << num_bits).collect();
+            check(&values, num_bits);
+        }
+
+        // Pairs that differ only in their last bit.
+        for seed in 1..=20 {
+            let values: Vec<u64> = random_values(seed, 50)
+                .iter()
+                .flat_map(|v| [v & 0xfe, v & 0xfe | 1])
+                .collect();
+            check(&values, 8);
+        }
+    }
 }
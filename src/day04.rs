@@ -1,65 +1,191 @@
 use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
 use std::fs;
 
+use crate::repl::Repl;
+
 #[derive(Copy, Clone, Default)]
 struct BoardCell {
     number: u32,
     marked: bool,
 }
 
-type BoardCells = [[BoardCell; 5]; 5];
+/// Patterns of marked cells that complete a board.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum WinCondition {
+    /// Any full row or column.
+    Lines,
+
+    /// Either full diagonal. Only square boards have diagonals.
+    Diagonals,
+
+    /// All four corners.
+    FourCorners,
+
+    /// Every cell.
+    FullHouse,
+}
+
+impl WinCondition {
+    fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "lines" => Ok(Self::Lines),
+            "diagonals" => Ok(Self::Diagonals),
+            "corners" => Ok(Self::FourCorners),
+            "full" => Ok(Self::FullHouse),
+            _ => Err(format!("unknown win condition '{}'", s)),
+        }
+    }
+}
+
+impl fmt::Display for WinCondition {
+    /// Formats the value using the given formatter.
+    ///
+    /// * `f`     - Formatter
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Lines => write!(f, "lines"),
+            Self::Diagonals => write!(f, "diagonals"),
+            Self::FourCorners => write!(f, "corners"),
+            Self::FullHouse => write!(f, "full"),
+        }
+    }
+}
 
-#[derive(Clone, Default)]
+/// A board with counts of the marked cells in each line and pattern so wins
+/// are found without rescanning the board.
+#[derive(Clone)]
 struct Board {
-    cells: BoardCells,
+    cells: Vec<Vec<BoardCell>>,
+    row_hits: Vec<usize>,
+    col_hits: Vec<usize>,
+    diagonal_hits: [usize; 2],
+    corner_hits: usize,
+    marked: usize,
+
+    // The draw that completed the board if it has met the win conditions.
+    won: Option<usize>,
 }
 
 impl Board {
-    fn mark(&mut self, number: u32) {
-        // Let caller verify board is incomplete.
-        for r in 0..5 {
-            for c in 0..5 {
-                if self.cells[r][c].number == number {
-                    self.cells[r][c].marked = true;
-                }
-            }
+    fn new(cells: Vec<Vec<BoardCell>>) -> Self {
+        let (h, w) = (cells.len(), cells[0].len());
+        Self {
+            cells,
+            row_hits: vec![0; h],
+            col_hits: vec![0; w],
+            diagonal_hits: [0; 2],
+            corner_hits: 0,
+            marked: 0,
+            won: None,
         }
     }
 
-    fn score(&self, last_number: u32) -> u32 {
-        let mut score = 0_u32;
-        for r in 0..5 {
-            for c in 0..5 {
-                if !self.cells[r][c].marked {
-                    score += self.cells[r][c].number;
-                }
-            }
+    fn rows(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn cols(&self) -> usize {
+        self.cells[0].len()
+    }
+
+    fn is_square(&self) -> bool {
+        self.rows() == self.cols()
+    }
+
+    /// Number of distinct corner cells. Narrow boards share corners.
+    fn corners(&self) -> usize {
+        self.rows().min(2) * self.cols().min(2)
+    }
+
+    fn is_corner(&self, r: usize, c: usize) -> bool {
+        (r == 0 || r == self.rows() - 1) && (c == 0 || c == self.cols() - 1)
+    }
+
+    fn clear(&mut self) {
+        for cell in self.cells.iter_mut().flatten() {
+            cell.marked = false;
         }
-        score * last_number
+        self.row_hits.fill(0);
+        self.col_hits.fill(0);
+        self.diagonal_hits = [0; 2];
+        self.corner_hits = 0;
+        self.marked = 0;
+        self.won = None;
     }
 
-    fn is_complete(&self) -> bool {
-        for r in 0..5 {
-            let mut complete = true;
-            for c in 0..5 {
-                complete = complete && self.cells[r][c].marked
-            }
-            if complete {
-                return true;
-            }
+    /// Marks a cell and returns true if the board meets any of the win
+    /// conditions afterwards. Only the lines and patterns through the cell
+    /// are checked.
+    ///
+    /// * `r`              - Row.
+    /// * `c`              - Column.
+    /// * `win_conditions` - The win conditions.
+    fn mark(&mut self, r: usize, c: usize, win_conditions: &[WinCondition]) -> bool {
+        if self.cells[r][c].marked {
+            return false;
         }
+        self.cells[r][c].marked = true;
 
-        for c in 0..5 {
-            let mut complete = true;
-            for r in 0..5 {
-                complete = complete && self.cells[r][c].marked
-            }
-            if complete {
-                return true;
-            }
+        let w = self.cols();
+        self.row_hits[r] += 1;
+        self.col_hits[c] += 1;
+        if self.is_square() && r == c {
+            self.diagonal_hits[0] += 1;
+        }
+        if self.is_square() && r + c == w - 1 {
+            self.diagonal_hits[1] += 1;
         }
+        if self.is_corner(r, c) {
+            self.corner_hits += 1;
+        }
+        self.marked += 1;
+
+        win_conditions.iter().any(|&win| self.satisfies(win, r, c))
+    }
 
-        false
+    fn score(&self, last_number: u32) -> u32 {
+        let unmarked: u32 = self
+            .cells
+            .iter()
+            .flatten()
+            .filter(|cell| !cell.marked)
+            .map(|cell| cell.number)
+            .sum();
+        unmarked * last_number
+    }
+
+    /// Returns true if the marked cells match the pattern. Only the lines
+    /// through the cell that was just marked can have been completed.
+    ///
+    /// * `win` - The win condition.
+    /// * `r`   - Row of the cell that was just marked.
+    /// * `c`   - Column of the cell that was just marked.
+    fn satisfies(&self, win: WinCondition, r: usize, c: usize) -> bool {
+        let (h, w) = (self.rows(), self.cols());
+        match win {
+            WinCondition::Lines => self.row_hits[r] == w || self.col_hits[c] == h,
+            WinCondition::Diagonals => self.is_square() && self.diagonal_hits.contains(&h),
+            WinCondition::FourCorners => self.corner_hits == self.corners(),
+            WinCondition::FullHouse => self.marked == h * w,
+        }
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.cells.iter() {
+            for cell in row.iter() {
+                if cell.marked {
+                    write!(f, "[{:2}]", cell.number)?;
+                } else {
+                    write!(f, " {:2} ", cell.number)?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
     }
 }
 
@@ -67,6 +193,13 @@ impl Board {
 struct Bingo {
     draws: Vec<u32>,
     boards: Vec<Board>,
+    win_conditions: Vec<WinCondition>,
+
+    // Where each number appears as (board, row, column).
+    index: HashMap<u32, Vec<(usize, usize, usize)>>,
+
+    // Numbers drawn so far. A board's winning draw is an index into these.
+    drawn: Vec<u32>,
 }
 
 impl Bingo {
@@ -79,95 +212,344 @@ impl Bingo {
             .split(",")
             .map(|s| {
                 s.parse::<u32>()
-                    .expect(&format!("invalid non-numeric input {}", s))
+                    .unwrap_or_else(|_| panic!("invalid non-numeric input {}", s))
             })
             .collect();
 
+        // Boards are separated by blank lines and can be any size.
         let seperator = Regex::new(r"( +)").expect("invalid regex");
         let mut boards: Vec<Board> = vec![];
-        let mut i = 2; // skip blank line
-        while i < contents.len() {
-            let mut board = Board::default();
-
-            for r in 0..5 {
-                let row: Vec<u32> = seperator
-                    .split(contents[i].trim())
-                    .into_iter()
-                    .map(|s| {
-                        s.parse::<u32>()
-                            .expect(&format!("invalid non-numeric input {}", s))
-                    })
-                    .collect();
-                for c in 0..5 {
-                    board.cells[r][c].number = row[c];
+        for lines in contents[1..].split(|line| line.trim().is_empty()) {
+            if lines.is_empty() {
+                continue;
+            }
+
+            let cells: Vec<Vec<BoardCell>> = lines
+                .iter()
+                .map(|line| {
+                    seperator
+                        .split(line.trim())
+                        .map(|s| BoardCell {
+                            number: s
+                                .parse::<u32>()
+                                .unwrap_or_else(|_| panic!("invalid non-numeric input {}", s)),
+                            marked: false,
+                        })
+                        .collect()
+                })
+                .collect();
+
+            let cols = cells[0].len();
+            assert!(
+                cells.iter().all(|row| row.len() == cols),
+                "board {} has rows of different lengths",
+                boards.len()
+            );
+            boards.push(Board::new(cells));
+        }
+
+        let mut index: HashMap<u32, Vec<(usize, usize, usize)>> = HashMap::new();
+        for (j, board) in boards.iter().enumerate() {
+            for (r, row) in board.cells.iter().enumerate() {
+                for (c, cell) in row.iter().enumerate() {
+                    index.entry(cell.number).or_default().push((j, r, c));
                 }
-                i += 1;
             }
-            boards.push(board);
+        }
 
-            i += 1; // blank line
+        Self {
+            draws,
+            boards,
+            win_conditions: vec![WinCondition::Lines],
+            index,
+            drawn: vec![],
         }
+    }
 
-        Self { draws, boards }
+    /// Marks a number on the boards that haven't won yet and returns the
+    /// boards it completes in order.
+    ///
+    /// * `number` - The drawn number.
+    fn draw(&mut self, number: u32) -> Vec<usize> {
+        let draw = self.drawn.len();
+        self.drawn.push(number);
+        let mut winners = vec![];
+        if let Some(cells) = self.index.get(&number) {
+            for &(j, r, c) in cells {
+                // Boards that won on an earlier draw are left alone. A board
+                // can hold the number more than once so keep marking boards
+                // that win on this draw.
+                let board = &mut self.boards[j];
+                if board.won.is_some_and(|won| won != draw) {
+                    continue;
+                }
+                if board.mark(r, c, &self.win_conditions) && board.won.is_none() {
+                    board.won = Some(draw);
+                    winners.push(j);
+                }
+            }
+        }
+        winners
     }
-}
 
-pub fn part1(input_file: &str) {
-    let mut bingo = Bingo::new(input_file);
-    let n = bingo.draws.len();
-    let b = bingo.boards.len();
-    let mut completed_board: Option<usize> = None;
-    let mut last_number: u32 = 0;
+    /// Changes how boards win and replays the numbers drawn so far under the
+    /// new rules. Returns every win as (board, draw) in the order they happen.
+    ///
+    /// * `win_conditions` - The win conditions. Any one of them wins.
+    fn set_win_conditions(
+        &mut self,
+        win_conditions: Vec<WinCondition>,
+    ) -> Result<Vec<(usize, usize)>, String> {
+        if win_conditions.is_empty() {
+            return Err("expecting at least one win condition".to_string());
+        }
+        if win_conditions.contains(&WinCondition::Diagonals)
+            && !self.boards.iter().all(|board| board.is_square())
+        {
+            return Err("diagonals need square boards".to_string());
+        }
+        self.win_conditions = win_conditions;
 
-    for i in 0..n {
-        let number = bingo.draws[i];
-        for j in 0..b {
-            if bingo.boards[j].is_complete() {
-                continue;
-            }
+        // Boards stop marking once they win so the marks depend on the rules.
+        for board in self.boards.iter_mut() {
+            board.clear();
+        }
+        let mut wins = vec![];
+        for number in std::mem::take(&mut self.drawn) {
+            let draw = self.drawn.len();
+            wins.extend(self.draw(number).into_iter().map(|j| (j, draw)));
+        }
+        Ok(wins)
+    }
+}
 
-            bingo.boards[j].mark(number);
+impl Bingo {
+    /// Draws every number on fresh copies of the boards and records when
+    /// each board wins.
+    fn play(&self) -> Tournament {
+        let mut bingo = self.clone();
+        bingo.drawn.clear();
+        for board in bingo.boards.iter_mut() {
+            board.clear();
+        }
 
-            if bingo.boards[j].is_complete() {
-                completed_board = Some(j);
-                last_number = number;
-                break;
+        let mut wins: Vec<Win> = vec![];
+        for (draw, &number) in self.draws.iter().enumerate() {
+            for j in bingo.draw(number) {
+                wins.push(Win {
+                    board: j,
+                    draw,
+                    number,
+                    score: bingo.boards[j].score(number),
+                });
             }
         }
 
-        if completed_board.is_some() {
-            break;
+        let never_won = (0..bingo.boards.len())
+            .filter(|&j| bingo.boards[j].won.is_none())
+            .collect();
+        Tournament { wins, never_won }
+    }
+}
+
+/// A board completing on a draw.
+struct Win {
+    board: usize,
+
+    // Index of the winning draw.
+    draw: usize,
+
+    number: u32,
+    score: u32,
+}
+
+/// Every win in the order they happened and the boards that never won.
+struct Tournament {
+    wins: Vec<Win>,
+    never_won: Vec<usize>,
+}
+
+impl Tournament {
+    fn first(&self) -> Option<&Win> {
+        self.wins.first()
+    }
+
+    fn last(&self) -> Option<&Win> {
+        self.wins.last()
+    }
+}
+
+impl fmt::Display for Tournament {
+    /// Formats the value using the given formatter.
+    ///
+    /// * `f`     - Formatter
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>5} {:>6} {:>6} {:>7} {:>8}",
+            "rank", "board", "draw", "number", "score"
+        )?;
+        for (rank, win) in self.wins.iter().enumerate() {
+            write!(
+                f,
+                "\n{:>5} {:>6} {:>6} {:>7} {:>8}",
+                rank + 1,
+                win.board,
+                win.draw,
+                win.number,
+                win.score
+            )?;
+        }
+        match self.never_won.len() {
+            0 => write!(f, "\nevery board won"),
+            _ => write!(f, "\nnever won: {:?}", self.never_won),
         }
     }
+}
 
-    let first = completed_board.unwrap();
-    let score = bingo.boards[first].score(last_number);
-    println!("day 04: part 1 = {}", score);
+/// Interactive state for marking draws on the boards.
+pub struct BingoRepl {
+    bingo: Bingo,
+    next_draw: usize,
+    winners: Vec<usize>,
 }
 
-pub fn part2(input_file: &str) {
-    let mut bingo = Bingo::new(input_file);
-    let n = bingo.draws.len();
-    let b = bingo.boards.len();
-    let mut completed_board: Option<usize> = None;
-    let mut last_number: u32 = 0;
+impl BingoRepl {
+    pub fn new(input_file: &str) -> Self {
+        Self {
+            bingo: Bingo::new(input_file),
+            next_draw: 0,
+            winners: vec![],
+        }
+    }
 
-    for i in 0..n {
-        let number = bingo.draws[i];
-        for j in 0..b {
-            if bingo.boards[j].is_complete() {
-                continue;
+    /// Marks a number on all incomplete boards and reports boards that
+    /// became complete.
+    ///
+    /// * `number` - The drawn number.
+    fn draw(&mut self, number: u32) -> String {
+        let mut output = format!("drew {}", number);
+        for j in self.bingo.draw(number) {
+            self.winners.push(j);
+            let score = self.bingo.boards[j].score(number);
+            output += &format!("\nboard {} wins with score {}", j, score);
+        }
+        output
+    }
+
+    /// Replaces the winners with the ones found by replaying the draws under
+    /// new win conditions and reports the boards whose result changed.
+    ///
+    /// * `wins` - Every win as (board, draw) in the order they happen.
+    fn replayed(&mut self, wins: Vec<(usize, usize)>) -> String {
+        let mut output = format!(
+            "win conditions changed, replayed {} draws",
+            self.bingo.drawn.len()
+        );
+        for &(j, draw) in wins.iter() {
+            if !self.winners.contains(&j) {
+                let number = self.bingo.drawn[draw];
+                let score = self.bingo.boards[j].score(number);
+                output += &format!(
+                    "
+board {} now wins on draw {} ({}) with score {}",
+                    j, draw, number, score
+                );
             }
+        }
+        for &j in self.winners.iter() {
+            if !wins.iter().any(|&(k, _)| k == j) {
+                output += &format!(
+                    "
+board {} no longer wins",
+                    j
+                );
+            }
+        }
+        self.winners = wins.into_iter().map(|(j, _)| j).collect();
+        output
+    }
+}
 
-            bingo.boards[j].mark(number);
+impl Repl for BingoRepl {
+    fn help(&self) -> Vec<&'static str> {
+        vec![
+            "step           draw the next number from the input",
+            "draw <number>  draw the given number",
+            "show [board]   show all boards or the given board",
+            "winners        list boards in the order they won",
+            "tournament     rank the boards by when they win all draws",
+            "rules [win..]  show or change the win conditions:",
+            "               lines, diagonals, corners, full",
+        ]
+    }
 
-            if bingo.boards[j].is_complete() {
-                completed_board = Some(j);
-                last_number = number;
+    fn eval(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match (command, args) {
+            ("step", []) => {
+                let number = *self
+                    .bingo
+                    .draws
+                    .get(self.next_draw)
+                    .ok_or("no more numbers to draw")?;
+                self.next_draw += 1;
+                Ok(self.draw(number))
+            }
+            ("draw", [n]) => {
+                let number = n
+                    .parse::<u32>()
+                    .map_err(|_| format!("invalid number '{}'", n))?;
+                Ok(self.draw(number))
             }
+            ("show", []) => Ok(self
+                .bingo
+                .boards
+                .iter()
+                .enumerate()
+                .map(|(j, board)| format!("board {}\n{}", j, board))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            ("show", [n]) => {
+                let j = n
+                    .parse::<usize>()
+                    .map_err(|_| format!("invalid board '{}'", n))?;
+                let board = self
+                    .bingo
+                    .boards
+                    .get(j)
+                    .ok_or(format!("board {} does not exist", j))?;
+                Ok(format!("board {}\n{}", j, board))
+            }
+            ("winners", []) => Ok(format!("{:?}", self.winners)),
+            ("tournament", []) => Ok(self.bingo.play().to_string()),
+            ("rules", []) => Ok(self
+                .bingo
+                .win_conditions
+                .iter()
+                .map(|win| win.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")),
+            ("rules", wins) => {
+                let wins = wins
+                    .iter()
+                    .map(|s| WinCondition::parse(s))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let wins = self.bingo.set_win_conditions(wins)?;
+                Ok(self.replayed(wins))
+            }
+            _ => Err(format!("unknown command '{}'", command)),
         }
     }
-    let last = completed_board.unwrap();
-    let score = bingo.boards[last].score(last_number);
-    println!("day 04: part 2 = {}", score);
+}
+
+pub fn part1(input_file: &str) -> String {
+    let tournament = Bingo::new(input_file).play();
+    let first = tournament.first().expect("no board wins");
+    first.score.to_string()
+}
+
+pub fn part2(input_file: &str) -> String {
+    let tournament = Bingo::new(input_file).play();
+    let last = tournament.last().expect("no board wins");
+    last.score.to_string()
 }
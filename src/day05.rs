@@ -68,7 +68,7 @@ fn update_point_count(pc: &mut HashMap<Point, usize>, x: i32, y: i32) {
     }
 }
 
-pub fn part1(input_file: &str) {
+pub fn part1(input_file: &str) -> String {
     let lines = read(input_file);
 
     let mut point_count: HashMap<Point, usize> = HashMap::new();
@@ -95,10 +95,10 @@ pub fn part1(input_file: &str) {
     }
 
     let overlaps = point_count.values().filter(|&c| *c >= 2).count();
-    println!("day 05: part 1 = {}", overlaps);
+    overlaps.to_string()
 }
 
-pub fn part2(input_file: &str) {
+pub fn part2(input_file: &str) -> String {
     let lines = read(input_file);
 
     let mut point_count: HashMap<Point, usize> = HashMap::new();
@@ -143,5 +143,5 @@ pub fn part2(input_file: &str) {
     }
 
     let overlaps = point_count.values().filter(|&c| *c >= 2).count();
-    println!("day 05: part 2 = {}", overlaps);
+    overlaps.to_string()
 }
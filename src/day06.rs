@@ -1,5 +1,8 @@
+use std::fmt;
 use std::fs;
 
+use crate::simulation::Simulation;
+
 fn read(input_file: &str) -> Vec<usize> {
     let contents = fs::read_to_string(input_file).expect("Something went wrong reading the file");
     contents
@@ -27,21 +30,61 @@ fn simulate_growth_naive(input_file: &str, days: usize) -> usize {
     fish_timers.len()
 }
 
-fn simulate_growth_fast(input_file: &str, days: usize) -> usize {
-    let fish_timers = read(input_file);
+// Keep track of fish count by timer value.
+const N: usize = 9;
+
+#[derive(Copy, Clone)]
+pub struct School {
+    day: usize,
+    counts: [usize; N],
+}
+
+impl School {
+    fn size(&self) -> usize {
+        self.counts.iter().sum()
+    }
+}
 
-    // Keep track of fish count by timer value.
-    const N: usize = 9;
-    let mut current = [0_usize; N];
-    for t in fish_timers {
-        current[t] += 1;
+impl fmt::Display for School {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}: {} fish, by timer {:?}",
+            self.day,
+            self.size(),
+            self.counts
+        )
     }
+}
+
+pub struct Growth {
+    current: School,
 
     // Keep track of next fish count by timer value.
-    let mut next = [0_usize; N];
+    next: [usize; N],
+}
 
-    for _i in 0..days {
-        next.copy_from_slice(&current);
+impl Growth {
+    pub fn new(input_file: &str) -> Self {
+        let mut counts = [0_usize; N];
+        for t in read(input_file) {
+            counts[t] += 1;
+        }
+        Self {
+            current: School { day: 0, counts },
+            next: [0_usize; N],
+        }
+    }
+}
+
+impl Simulation for Growth {
+    type State = School;
+
+    fn step(&mut self) {
+        let current = &mut self.current.counts;
+        let next = &mut self.next;
+
+        next.copy_from_slice(current);
         for t in 0..N {
             if current[t] > 0 {
                 // There are n fishes with timer=t.
@@ -56,18 +99,31 @@ fn simulate_growth_fast(input_file: &str, days: usize) -> usize {
                 }
             }
         }
-        current.copy_from_slice(&next);
+        current.copy_from_slice(next);
+        self.current.day += 1;
     }
 
-    current.iter().sum()
+    fn state(&self) -> &School {
+        &self.current
+    }
+
+    fn restore(&mut self, snapshot: School) {
+        self.current = snapshot;
+    }
+}
+
+fn simulate_growth_fast(input_file: &str, days: usize) -> usize {
+    let mut growth = Growth::new(input_file);
+    growth.run_until(|school| school.day == days);
+    growth.state().size()
 }
 
-pub fn part1(input_file: &str) {
+pub fn part1(input_file: &str) -> String {
     let n = simulate_growth_fast(input_file, 80);
-    println!("day 06: part 1 = {}", n);
+    n.to_string()
 }
 
-pub fn part2(input_file: &str) {
+pub fn part2(input_file: &str) -> String {
     let n = simulate_growth_fast(input_file, 256);
-    println!("day 06: part 2 = {}", n);
+    n.to_string()
 }
@@ -16,19 +16,18 @@ fn median(v: &Vec<isize>) -> isize {
     sorted[mid]
 }
 
-pub fn part1(input_file: &str) {
+pub fn part1(input_file: &str) -> String {
     let positions = read(input_file);
     let p_median = median(&positions);
     let cost: isize = positions.iter().map(|p| (*p - p_median).abs()).sum();
-    println!("day 07: part 1 = cost={} at pos={}", cost, p_median);
+    cost.to_string()
 }
 
-pub fn part2(input_file: &str) {
+pub fn part2(input_file: &str) -> String {
     let positions = read(input_file);
     let min = *positions.iter().min().unwrap();
     let max = *positions.iter().max().unwrap();
     let mut best_cost: Option<isize> = None;
-    let mut best_pos: Option<isize> = None;
     for i in min..=max {
         let cost: isize = positions.iter().fold(0, |a, p| {
             let n = (p - i).abs();
@@ -38,17 +37,11 @@ pub fn part2(input_file: &str) {
         if let Some(bc) = best_cost {
             if cost < bc {
                 best_cost = Some(cost);
-                best_pos = Some(i as isize);
             }
         } else {
             best_cost = Some(cost);
-            best_pos = Some(i as isize);
         };
     }
 
-    println!(
-        "day 07: part 2 = cost={} at pos={}",
-        best_cost.unwrap(),
-        best_pos.unwrap()
-    );
+    best_cost.unwrap().to_string()
 }
@@ -166,7 +166,7 @@ fn match_output(output: &str, digits_to_str: &Vec<String>) -> Option<usize> {
     None
 }
 
-pub fn part1(input_file: &str) {
+pub fn part1(input_file: &str) -> String {
     let entries = read(input_file);
     let count_1478 = entries.iter().fold(0, |sum, entry| {
         sum + entry
@@ -175,10 +175,10 @@ pub fn part1(input_file: &str) {
             .filter(|output| signals_to_digits(&output).is_left())
             .count()
     });
-    println!("day 08: part 1 = {}", count_1478);
+    count_1478.to_string()
 }
 
-pub fn part2(input_file: &str) {
+pub fn part2(input_file: &str) -> String {
     let entries = read(input_file);
 
     let sum: usize = entries
@@ -196,5 +196,5 @@ pub fn part2(input_file: &str) {
         })
         .sum();
 
-    println!("day 08: part 2 = {}", sum);
+    sum.to_string()
 }
@@ -66,16 +66,16 @@ fn bfs_basin(
     })
 }
 
-pub fn part1(input_file: &str) {
+pub fn part1(input_file: &str) -> String {
     let heightmap = read(input_file);
     let lows = find_low_points(&heightmap);
     let sum = lows
         .iter()
         .fold(0_usize, |a, &(r, c)| a + heightmap[r][c] as usize + 1);
-    println!("day 09: part 1 = {}", sum);
+    sum.to_string()
 }
 
-pub fn part2(input_file: &str) {
+pub fn part2(input_file: &str) -> String {
     let heightmap = read(input_file);
     let lows = find_low_points(&heightmap);
 
@@ -94,5 +94,5 @@ pub fn part2(input_file: &str) {
 
     // Multiply top 3 sizes.
     let n = basin_sizes.iter().rev().take(3).fold(1, |a, s| a * s);
-    println!("day 09: part 2 = {}", n);
+    n.to_string()
 }
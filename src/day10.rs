@@ -69,24 +69,26 @@ fn parse(input: &String) -> Result<String, char> {
     Ok(autocomplete)
 }
 
-pub fn part_1_and_2(input_file: &str) {
+pub fn part1(input_file: &str) -> String {
     let lines = read(input_file);
-    let (completed, errors): (Vec<_>, Vec<_>) = lines.iter().map(parse).partition(Result::is_ok);
-
-    let error_score: usize = errors
+    let error_score: usize = lines
         .iter()
-        .map(|r| r.as_ref().unwrap_err())
-        .map(|&c| corrupted_score(c))
+        .map(parse)
+        .filter_map(Result::err)
+        .map(corrupted_score)
         .sum();
+    error_score.to_string()
+}
 
-    let mut completion_scores: Vec<usize> = completed
+pub fn part2(input_file: &str) -> String {
+    let lines = read(input_file);
+    let mut completion_scores: Vec<usize> = lines
         .iter()
-        .map(|r| r.as_ref().unwrap())
+        .map(parse)
+        .filter_map(Result::ok)
         .map(|s| autocomplete_score(&s))
         .collect();
     completion_scores.sort();
     let mid = completion_scores.len() / 2;
-
-    println!("day 10: part 1 = {}", error_score);
-    println!("day 10: part 2 = {}", completion_scores[mid]);
+    completion_scores[mid].to_string()
 }
@@ -1,5 +1,8 @@
+use std::fmt;
 use std::fs;
 
+use crate::simulation::Simulation;
+
 fn read(input_file: &str) -> Vec<Vec<u8>> {
     let contents = fs::read_to_string(input_file).expect("Something went wrong reading the file");
     contents
@@ -26,88 +29,86 @@ fn get_neighbours(i: usize, j: usize, h: usize, w: usize) -> Vec<(usize, usize)>
     .collect()
 }
 
-pub fn part1(input_file: &str) {
-    let mut current = read(input_file);
-
-    let h = current.len();
-    assert!(h > 0);
-
-    let w = current[0].len();
-    assert!(w > 0);
+#[derive(Clone)]
+pub struct Cavern {
+    energy: Vec<Vec<u8>>,
+    step: usize,
+    flashes: usize,
+    last_flashes: usize,
+}
 
-    let mut flashed = vec![vec![false; h]; w];
-    let mut next = vec![vec![0_u8; h]; w];
-    let mut count = 0;
+impl Cavern {
+    fn size(&self) -> usize {
+        self.energy.len() * self.energy[0].len()
+    }
+}
 
-    for _step in 0..100 {
-        for i in 0..next.len() {
-            for j in 0..next[i].len() {
-                next[i][j] = current[i][j] + 1;
+impl fmt::Display for Cavern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.energy.iter() {
+            for energy in row.iter() {
+                write!(f, "{}", energy)?;
             }
+            writeln!(f)?;
         }
+        write!(
+            f,
+            "step {}: {} flashes, {} in last step",
+            self.step, self.flashes, self.last_flashes
+        )
+    }
+}
 
-        loop {
-            let mut more_flashes = false;
-            for i in 0..next.len() {
-                for j in 0..next[i].len() {
-                    if !flashed[i][j] && next[i][j] > 9 {
-                        flashed[i][j] = true;
-                        count += 1;
-
-                        for (m, n) in get_neighbours(i, j, h, w) {
-                            next[m][n] += 1;
-
-                            if next[m][n] > 9 {
-                                more_flashes = true;
-                            }
-                        }
-                    }
-                }
-            }
-            if !more_flashes {
-                break;
-            }
-        }
+pub struct Octopuses {
+    current: Cavern,
+    flashed: Vec<Vec<bool>>,
+    next: Vec<Vec<u8>>,
+}
 
-        for i in 0..next.len() {
-            for j in 0..next[i].len() {
-                if flashed[i][j] {
-                    current[i][j] = 0;
-                    flashed[i][j] = false;
-                } else {
-                    current[i][j] = next[i][j];
-                }
-            }
+impl Octopuses {
+    pub fn new(input_file: &str) -> Self {
+        let energy = read(input_file);
+
+        let h = energy.len();
+        assert!(h > 0);
+
+        let w = energy[0].len();
+        assert!(w > 0);
+
+        Self {
+            current: Cavern {
+                energy,
+                step: 0,
+                flashes: 0,
+                last_flashes: 0,
+            },
+            flashed: vec![vec![false; w]; h],
+            next: vec![vec![0_u8; w]; h],
         }
     }
-
-    println!("day 11: part 1 = {}", count);
 }
 
-pub fn part2(input_file: &str) {
-    let mut current = read(input_file);
-
-    let h = current.len();
-    assert!(h > 0);
+impl Simulation for Octopuses {
+    type State = Cavern;
 
-    let w = current[0].len();
-    assert!(w > 0);
+    fn step(&mut self) {
+        let current = &mut self.current.energy;
+        let flashed = &mut self.flashed;
+        let next = &mut self.next;
 
-    let mut flashed = vec![vec![false; h]; w];
-    let mut next = vec![vec![0_u8; h]; w];
-    let mut step = 0;
+        let h = current.len();
+        let w = current[0].len();
 
-    loop {
-        for i in 0..next.len() {
-            for j in 0..next[i].len() {
+        for i in 0..h {
+            for j in 0..w {
                 next[i][j] = current[i][j] + 1;
             }
         }
 
         loop {
             let mut more_flashes = false;
-            for i in 0..next.len() {
-                for j in 0..next[i].len() {
+            for i in 0..h {
+                for j in 0..w {
                     if !flashed[i][j] && next[i][j] > 9 {
                         flashed[i][j] = true;
 
@@ -126,11 +127,9 @@ pub fn part2(input_file: &str) {
             }
         }
 
-        step += 1;
-
         let mut num_flashed = 0;
-        for i in 0..next.len() {
-            for j in 0..next[i].len() {
+        for i in 0..h {
+            for j in 0..w {
                 if flashed[i][j] {
                     current[i][j] = 0;
                     flashed[i][j] = false;
@@ -140,10 +139,29 @@ pub fn part2(input_file: &str) {
                 }
             }
         }
-        if num_flashed == w * h {
-            break;
-        }
+
+        self.current.step += 1;
+        self.current.flashes += num_flashed;
+        self.current.last_flashes = num_flashed;
+    }
+
+    fn state(&self) -> &Cavern {
+        &self.current
+    }
+
+    fn restore(&mut self, snapshot: Cavern) {
+        self.current = snapshot;
     }
+}
+
+pub fn part1(input_file: &str) -> String {
+    let mut octopuses = Octopuses::new(input_file);
+    octopuses.run_until(|cavern| cavern.step == 100);
+    octopuses.state().flashes.to_string()
+}
 
-    println!("day 11: part 2 = {}", step);
+pub fn part2(input_file: &str) -> String {
+    let mut octopuses = Octopuses::new(input_file);
+    octopuses.run_until(|cavern| cavern.step > 0 && cavern.last_flashes == cavern.size());
+    octopuses.state().step.to_string()
 }
@@ -3,6 +3,9 @@ use std::fs;
 
 use itertools::Itertools;
 
+use crate::repl::Repl;
+use crate::watchdog;
+
 struct Cave {
     name: String,
     is_start: bool,
@@ -59,39 +62,39 @@ impl Graph {
         self.edges.push(Edge(p0, p1));
     }
 
+    fn find_point(&self, pt: &str) -> Option<usize> {
+        self.points.iter().position(|p| p.name.eq(pt))
+    }
+
     fn find_paths<P>(&self, can_visit: &P) -> Vec<Vec<String>>
     where
         P: Fn(&VecDeque<usize>, usize) -> bool,
     {
-        let mut paths: Vec<Vec<String>> = vec![];
-
-        let starts = self
-            .edges
-            .iter()
-            .filter(|&e| self.points[e.0].is_start || self.points[e.1].is_start)
-            .map(|e| {
-                if self.points[e.0].is_start {
-                    (e.0, e.1)
-                } else {
-                    (e.1, e.0)
-                }
-            });
-
-        for (from, to) in starts {
-            let mut acc: Vec<Vec<usize>> = vec![];
-            let mut path: VecDeque<usize> = VecDeque::new();
-
-            path.push_back(from);
-            self.traverse(&mut acc, &mut path, to, &can_visit);
-            path.pop_back();
+        let start = self.find_point("start").expect("start cave not found");
+        let end = self.find_point("end").expect("end cave not found");
+        self.find_paths_between(start, end, can_visit)
+    }
 
-            for path in acc.iter() {
-                let path: Vec<String> = path.iter().map(|&i| self.points[i].name.clone()).collect();
-                paths.push(path);
+    fn find_paths_between<P>(&self, from: usize, goal: usize, can_visit: &P) -> Vec<Vec<String>>
+    where
+        P: Fn(&VecDeque<usize>, usize) -> bool,
+    {
+        let mut acc: Vec<Vec<usize>> = vec![];
+        let mut path: VecDeque<usize> = VecDeque::new();
+
+        path.push_back(from);
+        for e in self.edges.iter() {
+            if e.0 == from {
+                self.traverse(&mut acc, &mut path, e.1, goal, can_visit);
+            } else if e.1 == from {
+                self.traverse(&mut acc, &mut path, e.0, goal, can_visit);
             }
         }
+        path.pop_back();
 
-        paths
+        acc.iter()
+            .map(|path| path.iter().map(|&i| self.points[i].name.clone()).collect())
+            .collect()
     }
 
     fn traverse<P>(
@@ -99,6 +102,7 @@ impl Graph {
         paths: &mut Vec<Vec<usize>>,
         current_path: &mut VecDeque<usize>,
         to: usize,
+        goal: usize,
         can_visit: &P,
     ) where
         P: Fn(&VecDeque<usize>, usize) -> bool,
@@ -111,18 +115,23 @@ impl Graph {
         print!("{} -> {}\n", self.points[from].name, self.points[to].name);
         */
 
+        // Give up on the remaining paths once out of time.
+        if watchdog::cancelled() {
+            return;
+        }
+
         if can_visit(current_path, to) {
             current_path.push_back(to);
 
-            if self.points[to].is_end {
+            if to == goal {
                 let path: Vec<usize> = current_path.iter().map(|&p| p).collect();
                 paths.push(path);
             } else {
                 for e in self.edges.iter() {
                     if e.0 == to {
-                        self.traverse(paths, current_path, e.1, can_visit);
+                        self.traverse(paths, current_path, e.1, goal, can_visit);
                     } else if e.1 == to {
-                        self.traverse(paths, current_path, e.0, can_visit);
+                        self.traverse(paths, current_path, e.0, goal, can_visit);
                     }
                 }
             }
@@ -132,6 +141,69 @@ impl Graph {
     }
 }
 
+/// Interactive state for exploring the cave system.
+pub struct GraphRepl {
+    graph: Graph,
+}
+
+impl GraphRepl {
+    pub fn new(input_file: &str) -> Self {
+        Self {
+            graph: read(input_file),
+        }
+    }
+}
+
+impl Repl for GraphRepl {
+    fn help(&self) -> Vec<&'static str> {
+        vec![
+            "show             list the caves and their connections",
+            "paths <from> <to> list paths visiting small caves at most once",
+        ]
+    }
+
+    fn eval(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        let graph = &self.graph;
+        match (command, args) {
+            ("show", []) => Ok(graph
+                .points
+                .iter()
+                .enumerate()
+                .map(|(i, cave)| {
+                    let neighbours = graph
+                        .edges
+                        .iter()
+                        .filter_map(|e| match (e.0 == i, e.1 == i) {
+                            (true, _) => Some(graph.points[e.1].name.as_str()),
+                            (_, true) => Some(graph.points[e.0].name.as_str()),
+                            _ => None,
+                        })
+                        .join(",");
+                    format!("{} -> {}", cave.name, neighbours)
+                })
+                .join("\n")),
+            ("paths", [from, to]) => {
+                let from = graph
+                    .find_point(from)
+                    .ok_or(format!("cave '{}' not found", from))?;
+                let to = graph
+                    .find_point(to)
+                    .ok_or(format!("cave '{}' not found", to))?;
+
+                let can_visit = |current_path: &VecDeque<usize>, p: usize| {
+                    !current_path.contains(&p) || graph.points[p].is_big
+                };
+                let paths = graph.find_paths_between(from, to, &can_visit);
+
+                let mut output: Vec<String> = paths.iter().map(|path| path.join(",")).collect();
+                output.push(format!("{} paths", paths.len()));
+                Ok(output.join("\n"))
+            }
+            _ => Err(format!("unknown command '{}'", command)),
+        }
+    }
+}
+
 fn read(input_file: &str) -> Graph {
     let mut graph = Graph::new();
 
@@ -145,7 +217,7 @@ fn read(input_file: &str) -> Graph {
     graph
 }
 
-pub fn part1(input_file: &str) {
+pub fn part1(input_file: &str) -> String {
     let graph = read(input_file);
 
     let can_visit = |current_path: &VecDeque<usize>, p: usize| {
@@ -167,10 +239,10 @@ pub fn part1(input_file: &str) {
     }
     */
 
-    println!("day 12: part 1 = {}", paths.len());
+    paths.len().to_string()
 }
 
-pub fn part2(input_file: &str) {
+pub fn part2(input_file: &str) -> String {
     let graph = read(input_file);
 
     let can_visit = |current_path: &VecDeque<usize>, p: usize| {
@@ -216,5 +288,5 @@ pub fn part2(input_file: &str) {
     }
     */
 
-    println!("day 12: part 1 = {}", paths.len());
+    paths.len().to_string()
 }
@@ -3,6 +3,8 @@ use std::collections::HashSet;
 use std::fs;
 use std::hash::{Hash, Hasher};
 
+use crate::repl::Repl;
+
 #[derive(Copy, Clone)]
 enum Axis {
     X,
@@ -108,6 +110,95 @@ impl Origami {
             Axis::Y => self.fold_up(fold.value),
         };
     }
+
+    fn render(&self) -> Vec<String> {
+        let dim = self.get_dimensions();
+        (0..=dim.y)
+            .map(|y| {
+                (0..=dim.x)
+                    .map(|x| {
+                        if self.dots.contains(&Coord::new(x, y)) {
+                            '#'
+                        } else {
+                            ' '
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Interactive state for folding the transparent paper.
+pub struct OrigamiRepl {
+    origami: Origami,
+    folds: Vec<Fold>,
+    next_fold: usize,
+}
+
+impl OrigamiRepl {
+    pub fn new(input_file: &str) -> Self {
+        let (origami, folds) = read(input_file);
+        Self {
+            origami,
+            folds,
+            next_fold: 0,
+        }
+    }
+}
+
+impl Repl for OrigamiRepl {
+    fn help(&self) -> Vec<&'static str> {
+        vec![
+            "step          apply the next fold from the input",
+            "fold <a>=<n>  fold along the given line, e.g. fold x=5",
+            "show          draw the dots",
+            "count         count the visible dots",
+        ]
+    }
+
+    fn eval(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match (command, args) {
+            ("step", []) => {
+                let fold = self
+                    .folds
+                    .get(self.next_fold)
+                    .ok_or("no more folds in input")?;
+                self.origami.fold(fold);
+                self.next_fold += 1;
+                Ok(format!("{} dots visible", self.origami.visible_count()))
+            }
+            ("fold", [line]) => {
+                let (axis, value) = line
+                    .split('=')
+                    .collect_tuple()
+                    .ok_or(format!("invalid fold '{}'", line))?;
+                let axis = match axis {
+                    "x" => Axis::X,
+                    "y" => Axis::Y,
+                    _ => return Err(format!("invalid axis '{}'", axis)),
+                };
+                let value = value
+                    .parse::<usize>()
+                    .map_err(|_| format!("invalid value '{}'", value))?;
+
+                let dim = self.origami.get_dimensions();
+                let max = match axis {
+                    Axis::X => dim.x,
+                    Axis::Y => dim.y,
+                };
+                if max > 2 * value {
+                    return Err(format!("fold at {} would move dots past the edge", line));
+                }
+
+                self.origami.fold(&Fold { axis, value });
+                Ok(format!("{} dots visible", self.origami.visible_count()))
+            }
+            ("show", []) => Ok(self.origami.render().join("\n")),
+            ("count", []) => Ok(format!("{} dots visible", self.origami.visible_count())),
+            _ => Err(format!("unknown command '{}'", command)),
+        }
+    }
 }
 
 fn read(input_file: &str) -> (Origami, Vec<Fold>) {
@@ -144,33 +235,16 @@ fn read(input_file: &str) -> (Origami, Vec<Fold>) {
     (origami, folds)
 }
 
-pub fn part_1_and_2(input_file: &str) {
+pub fn part1(input_file: &str) -> String {
     let (mut origami, folds) = read(input_file);
     origami.fold(&folds[0]);
+    origami.visible_count().to_string()
+}
 
-    let count = origami.visible_count();
-    println!("day 13: part 1 = {}", count);
-
-    for fold in folds.iter().skip(1) {
+pub fn part2(input_file: &str) -> String {
+    let (mut origami, folds) = read(input_file);
+    for fold in folds.iter() {
         origami.fold(fold);
     }
-
-    let dim = origami.get_dimensions();
-    for y in 0..=dim.y {
-        if y == 0 {
-            print!("day 13: part 2 = ");
-        } else {
-            print!("                 ");
-        }
-
-        for x in 0..=dim.x {
-            if origami.dots.contains(&Coord::new(x, y)) {
-                print!("#");
-            } else {
-                print!(" ");
-            }
-        }
-
-        println!();
-    }
+    origami.render().join("\n")
 }
@@ -146,26 +146,16 @@ fn apply_insertion_rules(
     (least_common, most_common)
 }
 
-pub fn part1(input_file: &str) {
+pub fn part1(input_file: &str) -> String {
     let (template, insertion_rules) = read(input_file);
     let (least_common, most_common) = apply_insertion_rules(&template, &insertion_rules, 10);
 
-    println!(
-        "day 14: part 1 = {} - {} = {}",
-        most_common,
-        least_common,
-        most_common - least_common
-    );
+    (most_common - least_common).to_string()
 }
 
-pub fn part2(input_file: &str) {
+pub fn part2(input_file: &str) -> String {
     let (template, insertion_rules) = read(input_file);
     let (least_common, most_common) = apply_insertion_rules(&template, &insertion_rules, 40);
 
-    println!(
-        "day 14: part 2 = {} - {} = {}",
-        most_common,
-        least_common,
-        most_common - least_common
-    );
+    (most_common - least_common).to_string()
 }
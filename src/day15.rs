@@ -4,6 +4,8 @@ use std::fs;
 use std::hash::{Hash, Hasher};
 use std::{cmp::Ordering, collections::BinaryHeap, collections::HashMap};
 
+use crate::watchdog;
+
 type Grid = Vec<Vec<usize>>;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -117,6 +119,11 @@ where
     f_score.insert(*start, h(start, goal));
 
     while !open_set.is_empty() {
+        // Give up once out of time.
+        if watchdog::cancelled() {
+            break;
+        }
+
         // Get the node in open_set having the lowest f_score[] value. This
         // operation can occur in O(1) time if open_set is a min-heap or a
         // priority queue.
@@ -148,11 +155,11 @@ where
         }
     }
 
-    // Open set is empty but goal was never reached.
+    // Open set is empty (or search was cancelled) but goal was never reached.
     vec![]
 }
 
-pub fn part1(input_file: &str) {
+pub fn part1(input_file: &str) -> String {
     let grid = read(input_file);
     let h = grid.len();
     let w = grid[0].len();
@@ -174,12 +181,12 @@ pub fn part1(input_file: &str) {
     );
     //println!("{:?}", best);
 
-    let mut risk: usize = best.iter().map(|p| grid[p.y][p.x]).sum();
-    risk -= grid[0][0]; // Start is not entered. Skip risk.
-    println!("day 15: part 1 = {}", risk);
+    // Start is not entered. Skip risk.
+    let risk: usize = best.iter().skip(1).map(|p| grid[p.y][p.x]).sum();
+    risk.to_string()
 }
 
-pub fn part2(input_file: &str) {
+pub fn part2(input_file: &str) -> String {
     let grid = read(input_file);
     let mut grid2 = grid.clone();
 
@@ -231,7 +238,7 @@ pub fn part2(input_file: &str) {
     );
     //println!("{:?}", best);
 
-    let mut risk: usize = best.iter().map(|p| grid2[p.y][p.x]).sum();
-    risk -= grid2[0][0]; // Start is not entered. Skip risk.
-    println!("day 15: part 2 = {}", risk);
+    // Start is not entered. Skip risk.
+    let risk: usize = best.iter().skip(1).map(|p| grid2[p.y][p.x]).sum();
+    risk.to_string()
 }
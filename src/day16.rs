@@ -1,4 +1,6 @@
-use std::{fmt, fs, iter::repeat};
+use std::{fmt, fs, iter::repeat, ops::Range};
+
+use crate::repl::Repl;
 
 enum Op {
     Sum,
@@ -57,11 +59,25 @@ impl Packet {
     /// NOTE: The returned position is relative to start of the given slice.
     ///
     /// * `s` - The hex data.
-    fn parse(s: &str) -> (Self, usize) {
+    fn parse(s: &str) -> Result<(Self, usize), String> {
         let bin = Self::hex_str_to_bin(s);
         Self::parse_bin(&bin)
     }
 
+    /// Returns the bits in the given range or an error if the data ends
+    /// before the range does.
+    ///
+    /// * `s`     - The binary data.
+    /// * `range` - The range of bits.
+    fn bits(s: &str, range: Range<usize>) -> Result<&str, String> {
+        s.get(range.clone()).ok_or(format!(
+            "truncated packet: expecting bits {}..{} but only {} remain",
+            range.start,
+            range.end,
+            s.len()
+        ))
+    }
+
     /// Parses a binary string containing a sequence that encodes a
     /// numeric expression and returns the decoded expression tree along
     /// with the next position in the string to begin parsing.
@@ -69,13 +85,13 @@ impl Packet {
     /// NOTE: The returned position is relative to start of the given slice.
     ///
     /// * `s` - The binary data.
-    fn parse_bin(s: &str) -> (Self, usize) {
-        let version = Self::bin_str_to_decimal(&s[0..3]);
-        let packet_type = Self::bin_str_to_decimal(&s[3..6]);
+    fn parse_bin(s: &str) -> Result<(Self, usize), String> {
+        let version = Self::bin_str_to_decimal(Self::bits(s, 0..3)?);
+        let packet_type = Self::bin_str_to_decimal(Self::bits(s, 3..6)?);
 
         match packet_type {
             4 => Packet::parse_literal(version, s),
-            _ => Packet::parse_operator(version, packet_type.into(), s),
+            _ => Packet::parse_operator(version, packet_type, s),
         }
     }
 
@@ -85,22 +101,12 @@ impl Packet {
     /// NOTE: The returned position is relative to start of the given slice.
     ///
     /// * `s` - The binary data.
-    fn parse_literal(version: u8, s: &str) -> (Self, usize) {
-        let n = s.len();
+    fn parse_literal(version: u8, s: &str) -> Result<(Self, usize), String> {
         let mut value = String::new();
         let mut i = 6; // Skip version + type ID
-        while i < n {
-            let is_end = match s.get(i..=i).unwrap() {
-                "1" => false,
-                "0" => true,
-                s => panic!(
-                    "Expecting '0' or '1' prefix for literal group. Found '{}'.",
-                    s
-                ),
-            };
-
-            let group = &s[i + 1..i + 5];
-            value += group;
+        loop {
+            let is_end = Self::bits(s, i..i + 1)? == "0";
+            value += Self::bits(s, i + 1..i + 5)?;
             i += 5;
 
             if is_end {
@@ -109,9 +115,9 @@ impl Packet {
             }
         }
 
-        let value = u64::from_str_radix(&value, 2).unwrap();
-        let next = if i < n { i } else { n };
-        (Self::Literal(version, value), next)
+        let value = u64::from_str_radix(&value, 2)
+            .map_err(|_| format!("literal value {} does not fit in 64 bits", value))?;
+        Ok((Self::Literal(version, value), i))
     }
 
     /// Parses an operator and returns the decoded expression tree along
@@ -120,32 +126,31 @@ impl Packet {
     /// NOTE: The returned position is relative to start of the given slice.
     ///
     /// * `s` - The binary data.
-    fn parse_operator(version: u8, packet_type: u8, s: &str) -> (Self, usize) {
+    fn parse_operator(version: u8, packet_type: u8, s: &str) -> Result<(Self, usize), String> {
         // Skip version + type ID
-        let (sub_packets, next) = match &s[6..=6] {
+        let (sub_packets, next) = match Self::bits(s, 6..7)? {
             "0" => {
                 // Next 15 bits give total length in bits of sub-packets.
-                let nbits = usize::from_str_radix(&s[7..22], 2).unwrap();
+                let nbits = usize::from_str_radix(Self::bits(s, 7..22)?, 2).unwrap();
                 let end = 22 + nbits; // not-inclusive
 
-                let (packets, mut next) = Self::parse_sub_packets(&s[22..end]);
+                let (packets, mut next) = Self::parse_sub_packets(Self::bits(s, 22..end)?)?;
                 next += 22;
                 (packets, next)
             }
-            "1" => {
+            _ => {
                 // Next 11 bits give count of sub-packets.
-                let count = usize::from_str_radix(&s[7..18], 2).unwrap();
-                let (packets, mut next) = Self::parse_sub_packets_by_count(&s[18..], count);
+                let count = usize::from_str_radix(Self::bits(s, 7..18)?, 2).unwrap();
+                let (packets, mut next) = Self::parse_sub_packets_by_count(&s[18..], count)?;
                 next += 18;
                 (packets, next)
             }
-            s => panic!("Expecting '0' or '1' for length type ID. Found '{}'.", s),
         };
 
-        (
+        Ok((
             Self::Operator(version, packet_type.into(), sub_packets),
             next,
-        )
+        ))
     }
 
     /// Parses all sub-packets in a binary sequence and returns a list of decoded
@@ -154,15 +159,15 @@ impl Packet {
     /// NOTE: The returned position is relative to start of the given slice.
     ///
     /// * `s` - The binary data.
-    fn parse_sub_packets(s: &str) -> (Vec<Self>, usize) {
+    fn parse_sub_packets(s: &str) -> Result<(Vec<Self>, usize), String> {
         let mut packets: Vec<Self> = vec![];
         let mut i = 0;
         while i < s.len() {
-            let (packet, next) = Self::parse_bin(&s[i..]);
+            let (packet, next) = Self::parse_bin(&s[i..])?;
             packets.push(packet);
             i += next;
         }
-        (packets, i)
+        Ok((packets, i))
     }
 
     /// Parses givn count of sub-packets in a binary sequence and returns a list
@@ -172,17 +177,17 @@ impl Packet {
     /// NOTE: The returned position is relative to start of the given slice.
     ///
     /// * `s` - The binary data.
-    fn parse_sub_packets_by_count(s: &str, count: usize) -> (Vec<Self>, usize) {
+    fn parse_sub_packets_by_count(s: &str, count: usize) -> Result<(Vec<Self>, usize), String> {
         let mut packets: Vec<Self> = vec![];
         let mut i = 0;
         let mut n = 0;
         while n < count {
-            let (packet, next) = Self::parse_bin(&s[i..]);
+            let (packet, next) = Self::parse_bin(&s[i..])?;
             packets.push(packet);
             i += next;
             n += 1;
         }
-        (packets, i)
+        Ok((packets, i))
     }
 
     /// Converts a hexadecimal value between 0 - F to its corresponding 4-bit
@@ -255,7 +260,7 @@ impl Packet {
                 let mut result = 0_u64;
                 for p in packets {
                     let v = p.evaluate()?;
-                    result += v;
+                    result = result.checked_add(v).ok_or("sum overflows 64 bits")?;
                 }
                 Ok(result)
             }
@@ -263,10 +268,13 @@ impl Packet {
                 let mut result = 1_u64;
                 for p in packets {
                     let v = p.evaluate()?;
-                    result *= v;
+                    result = result.checked_mul(v).ok_or("product overflows 64 bits")?;
                 }
                 Ok(result)
             }
+            Self::Operator(_, op @ (Op::Minimum | Op::Maximum), packets) if packets.is_empty() => {
+                Err(format!("'{}' needs at least one sub-packet", op))
+            }
             Self::Operator(_, Op::Minimum, packets) => {
                 let mut result = packets[0].evaluate()?;
                 for p in packets.iter().skip(1) {
@@ -283,9 +291,14 @@ impl Packet {
                 }
                 Ok(result)
             }
+            Self::Operator(_, op, packets) if packets.len() != 2 => Err(format!(
+                "'{}' needs exactly two sub-packets but found {}",
+                op,
+                packets.len()
+            )),
             Self::Operator(_, op, packets) => {
-                let a = packets[0].evaluate();
-                let b = packets[1].evaluate();
+                let a = packets[0].evaluate()?;
+                let b = packets[1].evaluate()?;
                 match op {
                     Op::GreaterThan => Ok((a > b) as u64),
                     Op::LessThan => Ok((a < b) as u64),
@@ -338,37 +351,85 @@ fn read(input_file: &str) -> Vec<String> {
     content.lines().map(|s| s.to_string()).collect()
 }
 
-pub fn part1(input_file: &str) {
-    println!("day 16: part 1");
-    let tx = read(input_file);
-    for s in tx {
-        let (p, _) = Packet::parse(&s);
-        let sum: usize = p.get_versions().iter().map(|&v| v as usize).sum();
-        if s.len() < 20 {
-            println!("{}", s);
-        } else {
-            println!("{}...", &s[0..20]);
+/// Interactive state for decoding and evaluating transmissions.
+pub struct PacketRepl {
+    transmissions: Vec<String>,
+}
+
+impl PacketRepl {
+    pub fn new(input_file: &str) -> Self {
+        Self {
+            transmissions: read(input_file),
+        }
+    }
+
+    /// Returns the given transmission or all transmissions from the input.
+    ///
+    /// * `args` - Optional hex transmission.
+    fn transmissions(&self, args: &[&str]) -> Result<Vec<String>, String> {
+        match args {
+            [] => Ok(self.transmissions.clone()),
+            [hex]
+                if hex
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || ('A'..='F').contains(&c)) =>
+            {
+                Ok(vec![hex.to_string()])
+            }
+            [hex] => Err(format!("invalid hex '{}'", hex)),
+            _ => Err("expecting at most one transmission".to_string()),
         }
-        println!("{}", p);
-        println!("sum versions = {}", sum);
-        println!();
     }
 }
 
-pub fn part2(input_file: &str) {
-    println!("day 16: part 2");
-    let tx = read(input_file);
-    for s in tx {
-        let (p, _) = Packet::parse(&s);
-        match p.evaluate() {
-            Ok(r) => {
-                if s.len() < 20 {
-                    println!("{} = {}", s, r);
-                } else {
-                    println!("{}... = {}", &s[0..20], r);
+impl Repl for PacketRepl {
+    fn help(&self) -> Vec<&'static str> {
+        vec![
+            "show [hex]  print the decoded packets",
+            "eval [hex]  evaluate the decoded packets",
+        ]
+    }
+
+    fn eval(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        let mut output: Vec<String> = vec![];
+        match command {
+            "show" => {
+                for s in self.transmissions(args)? {
+                    let (p, _) = Packet::parse(&s)?;
+                    output.push(format!("{}", p));
+                }
+            }
+            "eval" => {
+                for s in self.transmissions(args)? {
+                    let (p, _) = Packet::parse(&s)?;
+                    output.push(format!("{} = {}", s, p.evaluate()?));
                 }
             }
-            Err(e) => println!("{}", e),
+            _ => return Err(format!("unknown command '{}'", command)),
         }
+        Ok(output.join("\n"))
     }
 }
+
+pub fn part1(input_file: &str) -> String {
+    let tx = read(input_file);
+    tx.iter()
+        .map(|s| {
+            let (p, _) = Packet::parse(s).unwrap_or_else(|e| panic!("{}", e));
+            let sum: usize = p.get_versions().iter().map(|&v| v as usize).sum();
+            sum.to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn part2(input_file: &str) -> String {
+    let tx = read(input_file);
+    tx.iter()
+        .map(|s| match Packet::parse(s).and_then(|(p, _)| p.evaluate()) {
+            Ok(r) => r.to_string(),
+            Err(e) => e,
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -1,6 +1,8 @@
 use regex::{Captures, Regex};
 use std::fs;
 
+use crate::simulation::Simulation;
+
 #[derive(Copy, Clone, Debug)]
 struct Target {
     x1: i32,
@@ -83,16 +85,14 @@ fn read(input_file: &str) -> Target {
  * The highest point happens when v_y drops to 0 and using the table above:
  * => p_y = sum(vy) = sum(-target_min_y - 1)
  */
-pub fn part1(input_file: &str) {
-    println!("day 17: part 1");
+pub fn part1(input_file: &str) -> String {
     let target = read(input_file);
     let target_min_y = -target.y1.min(target.y2) - 1;
     let p_y = sum(target_min_y);
-    println!("Highest possible y = {p_y}");
+    p_y.to_string()
 }
 
-pub fn part2(input_file: &str) {
-    println!("day 17: part 2");
+pub fn part2(input_file: &str) -> String {
     let target = read(input_file);
 
     let x = target.x1.max(target.x2);
@@ -107,38 +107,70 @@ pub fn part2(input_file: &str) {
         }
     }
 
-    println!("Distinct velocities {}", velocities.len());
+    velocities.len().to_string()
 }
 
-fn hits(target: &Target, mut vx: i32, mut vy: i32) -> bool {
-    let mut x: i32 = 0;
-    let mut y: i32 = 0;
-    loop {
-        // Check out of bounds
-        if x > target.x2 || y < target.y1 {
-            return false;
-        }
-        if vx == 0 && (x < target.x1 || x > target.x2) {
-            return false;
-        }
+#[derive(Copy, Clone, Debug)]
+struct Probe {
+    x: i32,
+    y: i32,
+    vx: i32,
+    vy: i32,
+}
 
-        // Check within target
-        if x >= target.x1 && x <= target.x2 && y >= target.y1 && y <= target.y2 {
-            return true;
+impl Probe {
+    fn within(&self, target: &Target) -> bool {
+        self.x >= target.x1 && self.x <= target.x2 && self.y >= target.y1 && self.y <= target.y2
+    }
+
+    fn missed(&self, target: &Target) -> bool {
+        // Past the target, or stopped moving forward before reaching it.
+        self.x > target.x2 || self.y < target.y1 || (self.vx == 0 && self.x < target.x1)
+    }
+}
+
+struct Trajectory {
+    probe: Probe,
+}
+
+impl Trajectory {
+    fn new(vx: i32, vy: i32) -> Self {
+        Self {
+            probe: Probe { x: 0, y: 0, vx, vy },
         }
+    }
+}
 
-        // Continue trajectory
-        x += vx;
-        y += vy;
+impl Simulation for Trajectory {
+    type State = Probe;
 
-        if vx > 0 {
-            vx -= 1;
+    fn step(&mut self) {
+        let p = &mut self.probe;
+        p.x += p.vx;
+        p.y += p.vy;
+
+        if p.vx > 0 {
+            p.vx -= 1;
         }
 
-        vy -= 1;
+        p.vy -= 1;
+    }
+
+    fn state(&self) -> &Probe {
+        &self.probe
+    }
+
+    fn restore(&mut self, snapshot: Probe) {
+        self.probe = snapshot;
     }
 }
 
+fn hits(target: &Target, vx: i32, vy: i32) -> bool {
+    let mut trajectory = Trajectory::new(vx, vy);
+    trajectory.run_until(|probe| probe.within(target) || probe.missed(target));
+    trajectory.state().within(target)
+}
+
 fn sum(n: i32) -> i32 {
     n * (n + 1) / 2
 }
@@ -0,0 +1,306 @@
+use std::{fmt, fs, ops::Add};
+
+#[derive(Clone)]
+enum Number {
+    Regular(u32),
+    Pair(Box<Number>, Box<Number>),
+}
+
+impl Number {
+    /// Parses a snailfish number like `[[1,2],3]`.
+    ///
+    /// * `s` - The snailfish number.
+    fn parse(s: &str) -> Self {
+        let (number, next) = Self::parse_at(s.as_bytes(), 0);
+        assert!(next == s.len(), "unexpected trailing input in '{}'", s);
+        number
+    }
+
+    /// Parses a snailfish number starting at the given position and returns
+    /// the decoded tree along with the next position to begin parsing.
+    ///
+    /// * `s` - The snailfish number.
+    /// * `i` - Position to start parsing.
+    fn parse_at(s: &[u8], i: usize) -> (Self, usize) {
+        match s.get(i) {
+            Some(b'[') => {
+                let (left, i) = Self::parse_at(s, i + 1);
+                Self::expect(s, i, b',');
+                let (right, i) = Self::parse_at(s, i + 1);
+                Self::expect(s, i, b']');
+                (Self::Pair(Box::new(left), Box::new(right)), i + 1)
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let end = i + s[i..].iter().take_while(|c| c.is_ascii_digit()).count();
+                let value = std::str::from_utf8(&s[i..end]).unwrap().parse().unwrap();
+                (Self::Regular(value), end)
+            }
+            Some(&c) => panic!("invalid character '{}' at {}", c as char, i),
+            None => panic!("unexpected end of input"),
+        }
+    }
+
+    /// Panics if the character at the given position is not the expected one.
+    ///
+    /// * `s`        - The snailfish number.
+    /// * `i`        - Position to check.
+    /// * `expected` - The expected character.
+    fn expect(s: &[u8], i: usize, expected: u8) {
+        match s.get(i) {
+            Some(&c) if c == expected => {}
+            Some(&c) => panic!(
+                "expecting '{}' at {}. Found '{}'.",
+                expected as char, i, c as char
+            ),
+            None => panic!("expecting '{}' at {}", expected as char, i),
+        }
+    }
+
+    /// Repeatedly explodes and splits the number until neither applies.
+    fn reduce(&mut self) {
+        loop {
+            if self.explode(0).is_some() {
+                continue;
+            }
+            if self.split() {
+                continue;
+            }
+            break;
+        }
+    }
+
+    /// Explodes the leftmost pair nested inside four pairs. Returns `None` if
+    /// nothing exploded or the values that still need to be added to the
+    /// nearest regular numbers on the left and right.
+    ///
+    /// * `depth` - Number of pairs enclosing this one.
+    fn explode(&mut self, depth: usize) -> Option<(Option<u32>, Option<u32>)> {
+        let (left, right) = match self {
+            Self::Regular(_) => return None,
+            Self::Pair(left, right) => (left, right),
+        };
+
+        if depth >= 4 {
+            if let (Self::Regular(a), Self::Regular(b)) = (left.as_ref(), right.as_ref()) {
+                let exploded = (Some(*a), Some(*b));
+                *self = Self::Regular(0);
+                return Some(exploded);
+            }
+        }
+
+        if let Some((a, b)) = left.explode(depth + 1) {
+            if let Some(b) = b {
+                right.add_leftmost(b);
+            }
+            return Some((a, None));
+        }
+
+        if let Some((a, b)) = right.explode(depth + 1) {
+            if let Some(a) = a {
+                left.add_rightmost(a);
+            }
+            return Some((None, b));
+        }
+
+        None
+    }
+
+    fn add_leftmost(&mut self, value: u32) {
+        match self {
+            Self::Regular(v) => *v += value,
+            Self::Pair(left, _) => left.add_leftmost(value),
+        }
+    }
+
+    fn add_rightmost(&mut self, value: u32) {
+        match self {
+            Self::Regular(v) => *v += value,
+            Self::Pair(_, right) => right.add_rightmost(value),
+        }
+    }
+
+    /// Splits the leftmost regular number that is 10 or greater. Returns true
+    /// if a number was split.
+    fn split(&mut self) -> bool {
+        match self {
+            Self::Regular(v) if *v >= 10 => {
+                let left = Self::Regular(*v / 2);
+                let right = Self::Regular(v.div_ceil(2));
+                *self = Self::Pair(Box::new(left), Box::new(right));
+                true
+            }
+            Self::Regular(_) => false,
+            Self::Pair(left, right) => left.split() || right.split(),
+        }
+    }
+
+    fn magnitude(&self) -> u32 {
+        match self {
+            Self::Regular(v) => *v,
+            Self::Pair(left, right) => 3 * left.magnitude() + 2 * right.magnitude(),
+        }
+    }
+}
+
+impl Add for Number {
+    type Output = Self;
+
+    /// Adds two snailfish numbers and reduces the result.
+    ///
+    /// * `other` - The right hand side.
+    fn add(self, other: Self) -> Self {
+        let mut sum = Self::Pair(Box::new(self), Box::new(other));
+        sum.reduce();
+        sum
+    }
+}
+
+impl fmt::Display for Number {
+    /// Formats the value using the given formatter.
+    ///
+    /// * `f`     - Formatter
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Regular(v) => write!(f, "{}", v),
+            Self::Pair(left, right) => write!(f, "[{},{}]", left, right),
+        }
+    }
+}
+
+fn read(input_file: &str) -> Vec<Number> {
+    let contents = fs::read_to_string(input_file).expect("Something went wrong reading the file");
+    contents.lines().map(Number::parse).collect()
+}
+
+pub fn part1(input_file: &str) -> String {
+    let numbers = read(input_file);
+    let sum = numbers
+        .into_iter()
+        .reduce(|a, b| a + b)
+        .expect("no snailfish numbers in input");
+    sum.magnitude().to_string()
+}
+
+pub fn part2(input_file: &str) -> String {
+    let numbers = read(input_file);
+
+    // Addition is not commutative so try both orders.
+    let mut largest = 0;
+    for (i, a) in numbers.iter().enumerate() {
+        for (j, b) in numbers.iter().enumerate() {
+            if i != j {
+                largest = largest.max((a.clone() + b.clone()).magnitude());
+            }
+        }
+    }
+    largest.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Explodes the leftmost pair once and returns the result.
+    fn explode_once(s: &str) -> String {
+        let mut number = Number::parse(s);
+        assert!(number.explode(0).is_some(), "nothing exploded in '{}'", s);
+        number.to_string()
+    }
+
+    fn sum(numbers: &[&str]) -> String {
+        numbers
+            .iter()
+            .map(|s| Number::parse(s))
+            .reduce(|a, b| a + b)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn explode() {
+        let examples = [
+            ("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]"),
+            ("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]"),
+            ("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]"),
+            (
+                "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+            ),
+            (
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[7,0]]]]",
+            ),
+        ];
+        for (s, expected) in examples {
+            assert_eq!(explode_once(s), expected);
+        }
+    }
+
+    #[test]
+    fn split() {
+        let examples = [("10", "[5,5]"), ("11", "[5,6]"), ("12", "[6,6]")];
+        for (s, expected) in examples {
+            let mut number = Number::parse(s);
+            assert!(number.split());
+            assert_eq!(number.to_string(), expected);
+        }
+
+        let mut number = Number::parse("[[[[0,7],4],[15,[0,13]]],[1,1]]");
+        assert!(number.split());
+        assert_eq!(number.to_string(), "[[[[0,7],4],[[7,8],[0,13]]],[1,1]]");
+        assert!(number.split());
+        assert_eq!(number.to_string(), "[[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]");
+        assert!(!Number::parse("[[1,9],[8,5]]").split());
+    }
+
+    #[test]
+    fn add() {
+        assert_eq!(
+            sum(&["[[[[4,3],4],4],[7,[[8,4],9]]]", "[1,1]"]),
+            "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"
+        );
+        assert_eq!(
+            sum(&["[1,1]", "[2,2]", "[3,3]", "[4,4]", "[5,5]", "[6,6]"]),
+            "[[[[5,0],[7,4]],[5,5]],[6,6]]"
+        );
+        assert_eq!(
+            sum(&[
+                "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]",
+                "[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]",
+                "[[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]",
+                "[[[[2,4],7],[6,[0,5]]],[[[6,8],[2,8]],[[2,1],[4,5]]]]",
+                "[7,[5,[[3,8],[1,4]]]]",
+                "[[2,[2,2]],[8,[8,1]]]",
+                "[2,9]",
+                "[1,[[[9,3],9],[[9,0],[0,7]]]]",
+                "[[[5,[7,4]],7],1]",
+                "[[[[4,2],2],6],[8,7]]",
+            ]),
+            "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]"
+        );
+    }
+
+    #[test]
+    fn magnitude() {
+        let examples = [
+            ("[[1,2],[[3,4],5]]", 143),
+            ("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]", 1384),
+            ("[[[[1,1],[2,2]],[3,3]],[4,4]]", 445),
+            ("[[[[3,0],[5,3]],[4,4]],[5,5]]", 791),
+            ("[[[[5,0],[7,4]],[5,5]],[6,6]]", 1137),
+            (
+                "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]",
+                3488,
+            ),
+        ];
+        for (s, expected) in examples {
+            assert_eq!(Number::parse(s).magnitude(), expected);
+        }
+    }
+
+    #[test]
+    fn example() {
+        assert_eq!(part1("./inputs/day18/0.txt"), "4140");
+        assert_eq!(part2("./inputs/day18/0.txt"), "3993");
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::ops::{Add, Sub};
+
+use itertools::{iproduct, Itertools};
+
+/// Number of beacons two scanners need to share to be aligned.
+const MIN_OVERLAP: usize = 12;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+struct Point3 {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+impl Point3 {
+    fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    fn manhattan_distance(&self, other: &Self) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// Squared euclidean distance. Unlike the beacon coordinates this does
+    /// not change with the scanner's orientation.
+    fn distance_squared(&self, other: &Self) -> i64 {
+        let d = *self - *other;
+        let (x, y, z) = (d.x as i64, d.y as i64, d.z as i64);
+        x * x + y * y + z * z
+    }
+
+    fn rotate(&self, r: &Rotation) -> Self {
+        let v = [self.x, self.y, self.z];
+        let row = |i: usize| r[i][0] * v[0] + r[i][1] * v[1] + r[i][2] * v[2];
+        Self::new(row(0), row(1), row(2))
+    }
+}
+
+impl Add for Point3 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+/// A rotation matrix.
+type Rotation = [[i32; 3]; 3];
+
+/// Returns the 24 rotations that keep a scanner aligned to the axes. These
+/// are the signed permutation matrices with determinant 1; the other 24 would
+/// mirror the scanner.
+fn rotations() -> Vec<Rotation> {
+    let mut rotations = vec![];
+    for axes in (0..3).permutations(3) {
+        for signs in 0..8 {
+            let mut r = [[0; 3]; 3];
+            for (i, &axis) in axes.iter().enumerate() {
+                r[i][axis] = if signs & (1 << i) == 0 { 1 } else { -1 };
+            }
+
+            let det = r[0][0] * (r[1][1] * r[2][2] - r[1][2] * r[2][1])
+                - r[0][1] * (r[1][0] * r[2][2] - r[1][2] * r[2][0])
+                + r[0][2] * (r[1][0] * r[2][1] - r[1][1] * r[2][0]);
+            if det == 1 {
+                rotations.push(r);
+            }
+        }
+    }
+    rotations
+}
+
+struct Scanner {
+    beacons: Vec<Point3>,
+
+    // Squared distances between every pair of beacons. Scanners that see the
+    // same beacons have at least `MIN_OVERLAP choose 2` distances in common.
+    fingerprint: HashMap<i64, usize>,
+}
+
+impl Scanner {
+    fn new(beacons: Vec<Point3>) -> Self {
+        let fingerprint = beacons
+            .iter()
+            .tuple_combinations()
+            .map(|(a, b)| a.distance_squared(b))
+            .counts();
+        Self {
+            beacons,
+            fingerprint,
+        }
+    }
+
+    /// Counts the beacon pair distances shared with another scanner.
+    fn shared_distances(&self, other: &Self) -> usize {
+        self.fingerprint
+            .iter()
+            .map(|(d, &n)| n.min(*other.fingerprint.get(d).unwrap_or(&0)))
+            .sum()
+    }
+
+    /// Finds the orientation and position of `other` relative to this scanner
+    /// and returns the position along with the other scanner's beacons
+    /// transformed into this scanner's coordinates.
+    ///
+    /// * `other`     - The scanner to align.
+    /// * `rotations` - All axis aligned rotations.
+    fn align(&self, other: &Self, rotations: &[Rotation]) -> Option<(Point3, Vec<Point3>)> {
+        if self.shared_distances(other) < MIN_OVERLAP * (MIN_OVERLAP - 1) / 2 {
+            return None;
+        }
+
+        for r in rotations {
+            let rotated: Vec<Point3> = other.beacons.iter().map(|b| b.rotate(r)).collect();
+
+            // If enough beacons line up then the most common offset between
+            // beacons is the other scanner's position.
+            let offsets = iproduct!(self.beacons.iter(), rotated.iter())
+                .map(|(a, b)| *a - *b)
+                .counts();
+            if let Some((&offset, _)) = offsets.iter().find(|(_, &n)| n >= MIN_OVERLAP) {
+                let beacons = rotated.iter().map(|&b| b + offset).collect();
+                return Some((offset, beacons));
+            }
+        }
+        None
+    }
+}
+
+fn read(input_file: &str) -> Vec<Scanner> {
+    let contents = fs::read_to_string(input_file).expect("Something went wrong reading the file");
+
+    let mut scanners = vec![];
+    let mut beacons = vec![];
+    for row in contents.lines() {
+        if row.starts_with("---") {
+            continue;
+        }
+        if row.is_empty() {
+            scanners.push(Scanner::new(beacons));
+            beacons = vec![];
+            continue;
+        }
+
+        let (x, y, z) = row
+            .split(',')
+            .map(|v| {
+                v.parse::<i32>()
+                    .unwrap_or_else(|_| panic!("invalid non-numeric input {}", row))
+            })
+            .collect_tuple()
+            .unwrap_or_else(|| panic!("expecting x,y,z. Found '{}'.", row));
+        beacons.push(Point3::new(x, y, z));
+    }
+    if !beacons.is_empty() {
+        scanners.push(Scanner::new(beacons));
+    }
+    scanners
+}
+
+/// Aligns every scanner to scanner 0 and returns the scanner positions along
+/// with the distinct beacons.
+fn locate(scanners: Vec<Scanner>) -> (Vec<Point3>, HashSet<Point3>) {
+    let rotations = rotations();
+    let n = scanners.len();
+
+    // Scanners in scanner 0's coordinates as they are aligned.
+    let mut aligned: Vec<Option<Scanner>> = (0..n).map(|_| None).collect();
+    let mut positions = vec![Point3::default(); n];
+
+    let mut scanners: Vec<Option<Scanner>> = scanners.into_iter().map(Some).collect();
+    aligned[0] = scanners[0].take();
+
+    // Align unaligned scanners against each newly aligned one.
+    let mut queue = VecDeque::from([0]);
+    while let Some(i) = queue.pop_front() {
+        for j in 0..n {
+            let other = match &scanners[j] {
+                Some(other) => other,
+                None => continue,
+            };
+
+            let reference = aligned[i].as_ref().unwrap();
+            if let Some((position, beacons)) = reference.align(other, &rotations) {
+                positions[j] = position;
+                aligned[j] = Some(Scanner::new(beacons));
+                scanners[j] = None;
+                queue.push_back(j);
+            }
+        }
+    }
+
+    let unaligned: Vec<usize> = (0..n).filter(|&j| aligned[j].is_none()).collect();
+    assert!(
+        unaligned.is_empty(),
+        "unable to align scanners {:?}",
+        unaligned
+    );
+
+    let beacons = aligned
+        .iter()
+        .flatten()
+        .flat_map(|s| s.beacons.iter().copied())
+        .collect();
+    (positions, beacons)
+}
+
+pub fn part1(input_file: &str) -> String {
+    let (_, beacons) = locate(read(input_file));
+    beacons.len().to_string()
+}
+
+pub fn part2(input_file: &str) -> String {
+    let (positions, _) = locate(read(input_file));
+    let largest = positions
+        .iter()
+        .tuple_combinations()
+        .map(|(a, b)| a.manhattan_distance(b))
+        .max()
+        .unwrap_or(0);
+    largest.to_string()
+}
@@ -0,0 +1,97 @@
+use std::fs;
+
+use itertools::iproduct;
+
+struct Image {
+    pixels: Vec<Vec<bool>>,
+
+    // The image is infinite. Every pixel outside `pixels` has this value.
+    background: bool,
+}
+
+impl Image {
+    fn get(&self, r: isize, c: isize) -> bool {
+        let h = self.pixels.len() as isize;
+        let w = self.pixels[0].len() as isize;
+        if r >= 0 && r < h && c >= 0 && c < w {
+            self.pixels[r as usize][c as usize]
+        } else {
+            self.background
+        }
+    }
+
+    /// Returns the index into the enhancement algorithm for the 3x3 square
+    /// centered on the given pixel.
+    fn index(&self, r: isize, c: isize) -> usize {
+        iproduct!(-1..=1, -1..=1).fold(0, |index, (dr, dc)| {
+            (index << 1) | self.get(r + dr, c + dc) as usize
+        })
+    }
+
+    /// Applies the enhancement algorithm once. The image grows by one pixel on
+    /// each side since those pixels can see the original image.
+    ///
+    /// * `algorithm` - The image enhancement algorithm.
+    fn enhance(&self, algorithm: &[bool]) -> Self {
+        let h = self.pixels.len() as isize;
+        let w = self.pixels[0].len() as isize;
+
+        let pixels = (-1..h + 1)
+            .map(|r| (-1..w + 1).map(|c| algorithm[self.index(r, c)]).collect())
+            .collect();
+
+        // The background is all dark or all light so its 3x3 squares are
+        // either 0 or 511.
+        let background = algorithm[if self.background { 511 } else { 0 }];
+
+        Self { pixels, background }
+    }
+
+    fn lit_count(&self) -> usize {
+        assert!(!self.background, "infinitely many pixels are lit");
+        self.pixels.iter().flatten().filter(|&&p| p).count()
+    }
+}
+
+fn read(input_file: &str) -> (Vec<bool>, Image) {
+    let contents = fs::read_to_string(input_file).expect("Something went wrong reading the file");
+    let mut lines = contents.lines();
+
+    let algorithm: Vec<bool> = lines
+        .next()
+        .expect("missing image enhancement algorithm")
+        .chars()
+        .map(|c| c == '#')
+        .collect();
+    assert!(
+        algorithm.len() == 512,
+        "invalid image enhancement algorithm"
+    );
+
+    let pixels = lines
+        .skip(1) // skip blank line
+        .map(|row| row.chars().map(|c| c == '#').collect())
+        .collect();
+
+    let image = Image {
+        pixels,
+        background: false,
+    };
+    (algorithm, image)
+}
+
+fn enhance(input_file: &str, times: usize) -> usize {
+    let (algorithm, mut image) = read(input_file);
+    for _i in 0..times {
+        image = image.enhance(&algorithm);
+    }
+    image.lit_count()
+}
+
+pub fn part1(input_file: &str) -> String {
+    enhance(input_file, 2).to_string()
+}
+
+pub fn part2(input_file: &str) -> String {
+    enhance(input_file, 50).to_string()
+}
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::fs;
+
+use regex::Regex;
+
+lazy_static! {
+    static ref RE: Regex =
+        Regex::new(r"Player\s+(\d+)\s+starting\s+position:\s+(\d+)").expect("invalid regex");
+}
+
+fn read(input_file: &str) -> [usize; 2] {
+    let contents = fs::read_to_string(input_file).expect("Something went wrong reading the file");
+    let positions: Vec<usize> = contents
+        .lines()
+        .map(|row| {
+            let captures = RE
+                .captures(row)
+                .expect("Unable to extract with regex capture groups");
+            captures[2]
+                .parse()
+                .unwrap_or_else(|_| panic!("invalid starting position '{}'", row))
+        })
+        .collect();
+    positions
+        .try_into()
+        .expect("expecting starting positions for 2 players")
+}
+
+/// Moves a pawn around the circular track of spaces 1-10.
+///
+/// * `position` - Current space.
+/// * `roll`     - Total of the dice rolls.
+fn advance(position: usize, roll: usize) -> usize {
+    (position + roll - 1) % 10 + 1
+}
+
+/// A die that rolls 1, 2, 3, ..., 100 and then starts over.
+struct DeterministicDie {
+    next: usize,
+    rolls: usize,
+}
+
+impl DeterministicDie {
+    fn new() -> Self {
+        Self { next: 1, rolls: 0 }
+    }
+
+    fn roll(&mut self) -> usize {
+        let value = self.next;
+        self.next = self.next % 100 + 1;
+        self.rolls += 1;
+        value
+    }
+}
+
+pub fn part1(input_file: &str) -> String {
+    let mut positions = read(input_file);
+    let mut scores = [0_usize; 2];
+    let mut die = DeterministicDie::new();
+
+    let mut player = 0;
+    loop {
+        let roll = die.roll() + die.roll() + die.roll();
+        positions[player] = advance(positions[player], roll);
+        scores[player] += positions[player];
+        if scores[player] >= 1000 {
+            break;
+        }
+        player = 1 - player;
+    }
+
+    let loser = 1 - player;
+    (scores[loser] * die.rolls).to_string()
+}
+
+/// Game state from the point of view of the player about to move.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+struct Turn {
+    position: usize,
+    score: usize,
+    other_position: usize,
+    other_score: usize,
+}
+
+/// Counts the universes in which each player wins, starting with the player
+/// about to move. Many universes share the same state so the counts are
+/// memoised by state.
+///
+/// * `turn`  - The game state.
+/// * `rolls` - Number of universes for each total of three Dirac dice rolls.
+/// * `memo`  - Counts for states that have already been seen.
+fn count_wins(
+    turn: Turn,
+    rolls: &[(usize, u64)],
+    memo: &mut HashMap<Turn, (u64, u64)>,
+) -> (u64, u64) {
+    if let Some(&wins) = memo.get(&turn) {
+        return wins;
+    }
+
+    let mut wins = (0_u64, 0_u64);
+    for &(roll, universes) in rolls {
+        let position = advance(turn.position, roll);
+        let score = turn.score + position;
+        if score >= 21 {
+            wins.0 += universes;
+        } else {
+            // Other player moves next so their wins come first.
+            let next = Turn {
+                position: turn.other_position,
+                score: turn.other_score,
+                other_position: position,
+                other_score: score,
+            };
+            let (other_wins, own_wins) = count_wins(next, rolls, memo);
+            wins.0 += universes * own_wins;
+            wins.1 += universes * other_wins;
+        }
+    }
+
+    memo.insert(turn, wins);
+    wins
+}
+
+pub fn part2(input_file: &str) -> String {
+    let positions = read(input_file);
+
+    // Each turn splits into 27 universes but only 7 distinct totals.
+    let mut rolls: HashMap<usize, u64> = HashMap::new();
+    for a in 1..=3 {
+        for b in 1..=3 {
+            for c in 1..=3 {
+                *rolls.entry(a + b + c).or_insert(0) += 1;
+            }
+        }
+    }
+    let rolls: Vec<(usize, u64)> = rolls.into_iter().collect();
+
+    let turn = Turn {
+        position: positions[0],
+        score: 0,
+        other_position: positions[1],
+        other_score: 0,
+    };
+    let (wins1, wins2) = count_wins(turn, &rolls, &mut HashMap::new());
+    wins1.max(wins2).to_string()
+}
@@ -0,0 +1,137 @@
+use regex::{Captures, Regex};
+use std::fs;
+
+#[derive(Copy, Clone, Debug)]
+struct Cuboid {
+    x1: i64,
+    x2: i64,
+    y1: i64,
+    y2: i64,
+    z1: i64,
+    z2: i64,
+}
+impl Cuboid {
+    fn new(x1: i64, x2: i64, y1: i64, y2: i64, z1: i64, z2: i64) -> Self {
+        Self {
+            x1,
+            x2,
+            y1,
+            y2,
+            z1,
+            z2,
+        }
+    }
+
+    /// Returns the cubes in both cuboids or `None` if they don't overlap.
+    ///
+    /// * `other` - The other cuboid.
+    fn intersection(&self, other: &Self) -> Option<Self> {
+        let c = Self::new(
+            self.x1.max(other.x1),
+            self.x2.min(other.x2),
+            self.y1.max(other.y1),
+            self.y2.min(other.y2),
+            self.z1.max(other.z1),
+            self.z2.min(other.z2),
+        );
+        if c.x1 <= c.x2 && c.y1 <= c.y2 && c.z1 <= c.z2 {
+            Some(c)
+        } else {
+            None
+        }
+    }
+
+    /// Number of cubes in the cuboid. Bounds are inclusive.
+    fn volume(&self) -> i64 {
+        (self.x2 - self.x1 + 1) * (self.y2 - self.y1 + 1) * (self.z2 - self.z1 + 1)
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct RebootStep {
+    on: bool,
+    cuboid: Cuboid,
+}
+
+lazy_static! {
+    static ref RE: Regex =
+        Regex::new(r"(on|off)\s+x=(-?\d+)\.\.(-?\d+),y=(-?\d+)\.\.(-?\d+),z=(-?\d+)\.\.(-?\d+)")
+            .expect("invalid regex");
+}
+
+fn parse_capture(captures: &Captures, i: usize, name: &str) -> i64 {
+    captures
+        .get(i)
+        .unwrap_or_else(|| panic!("{name} not matched"))
+        .as_str()
+        .parse()
+        .unwrap_or_else(|_| panic!("invalid {name}. expecting integer"))
+}
+
+fn read(input_file: &str) -> Vec<RebootStep> {
+    let content = fs::read_to_string(input_file).expect("Something went wrong reading the file");
+
+    content
+        .lines()
+        .map(|row| {
+            let captures = RE
+                .captures(row)
+                .expect("Unable to extract with regex capture groups");
+
+            let on = &captures[1] == "on";
+            let x1 = parse_capture(&captures, 2, "x1");
+            let x2 = parse_capture(&captures, 3, "x2");
+            let y1 = parse_capture(&captures, 4, "y1");
+            let y2 = parse_capture(&captures, 5, "y2");
+            let z1 = parse_capture(&captures, 6, "z1");
+            let z2 = parse_capture(&captures, 7, "z2");
+
+            RebootStep {
+                on,
+                cuboid: Cuboid::new(x1, x2, y1, y2, z1, z2),
+            }
+        })
+        .collect()
+}
+
+/// Counts the cubes that are on after all the steps.
+///
+/// Keeps a list of cuboids with signs so that the signed volumes add up to
+/// the lit cubes (inclusion-exclusion). For each step the overlap with every
+/// existing cuboid is added with the opposite sign to cancel it out. Then an
+/// "on" step adds its own cuboid.
+///
+/// * `steps` - The reboot steps.
+fn count_on(steps: &[RebootStep]) -> i64 {
+    let mut cuboids: Vec<(Cuboid, i64)> = vec![];
+    for step in steps {
+        let mut added: Vec<(Cuboid, i64)> = cuboids
+            .iter()
+            .filter_map(|(c, sign)| step.cuboid.intersection(c).map(|i| (i, -sign)))
+            .collect();
+        if step.on {
+            added.push((step.cuboid, 1));
+        }
+        cuboids.append(&mut added);
+    }
+    cuboids.iter().map(|(c, sign)| c.volume() * sign).sum()
+}
+
+pub fn part1(input_file: &str) -> String {
+    let region = Cuboid::new(-50, 50, -50, 50, -50, 50);
+    let steps: Vec<RebootStep> = read(input_file)
+        .iter()
+        .filter_map(|step| {
+            step.cuboid.intersection(&region).map(|cuboid| RebootStep {
+                on: step.on,
+                cuboid,
+            })
+        })
+        .collect();
+    count_on(&steps).to_string()
+}
+
+pub fn part2(input_file: &str) -> String {
+    let steps = read(input_file);
+    count_on(&steps).to_string()
+}
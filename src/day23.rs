@@ -0,0 +1,261 @@
+use std::fmt;
+use std::fs;
+
+use crate::search;
+
+const HALLWAY: usize = 11;
+const ROOMS: usize = 4;
+const MAX_DEPTH: usize = 4;
+const EMPTY: u8 = 0;
+
+/// Energy used by amphipods A, B, C and D to move one step.
+const ENERGY: [usize; ROOMS] = [1, 10, 100, 1000];
+
+/// Extra rows unfolded from the diagram in part 2.
+const UNFOLDED: [&str; 2] = ["  #D#C#B#A#", "  #D#B#A#C#"];
+
+/// Hallway space just outside a room. Amphipods never stop here.
+fn entrance(room: usize) -> usize {
+    2 + 2 * room
+}
+
+/// The hallway and rooms. Each cell is empty or holds amphipod 1-4 (A-D).
+/// Hallway spaces come first followed by each room from top to bottom.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+struct Burrow {
+    cells: [u8; HALLWAY + ROOMS * MAX_DEPTH],
+    depth: usize,
+}
+
+impl Burrow {
+    fn parse(rows: &[&str]) -> Self {
+        // Skip the wall and hallway rows. Room rows end at the bottom wall.
+        let room_rows: Vec<&[u8]> = rows
+            .iter()
+            .skip(2)
+            .map(|row| row.as_bytes())
+            .take_while(|row| row.iter().any(|c| c.is_ascii_alphabetic()))
+            .collect();
+
+        let depth = room_rows.len();
+        assert!(depth > 0 && depth <= MAX_DEPTH, "invalid room depth");
+
+        let mut burrow = Self {
+            cells: [EMPTY; HALLWAY + ROOMS * MAX_DEPTH],
+            depth,
+        };
+        for (d, row) in room_rows.iter().enumerate() {
+            for room in 0..ROOMS {
+                let c = row[entrance(room) + 1];
+                assert!(
+                    (b'A'..=b'D').contains(&c),
+                    "invalid amphipod '{}'",
+                    c as char
+                );
+                burrow.cells[Self::slot(room, d)] = c - b'A' + 1;
+            }
+        }
+        burrow
+    }
+
+    /// Index of a space in a room. Depth 0 is next to the hallway.
+    fn slot(room: usize, d: usize) -> usize {
+        HALLWAY + room * MAX_DEPTH + d
+    }
+
+    fn room(&self, room: usize) -> &[u8] {
+        let top = Self::slot(room, 0);
+        &self.cells[top..top + self.depth]
+    }
+
+    /// Returns true if the room only holds the amphipods that belong there.
+    fn is_settled(&self, room: usize) -> bool {
+        self.room(room)
+            .iter()
+            .all(|&a| a == EMPTY || a as usize == room + 1)
+    }
+
+    fn is_organized(&self) -> bool {
+        (0..ROOMS).all(|room| self.room(room).iter().all(|&a| a as usize == room + 1))
+    }
+
+    /// Returns true if no amphipod is in the hallway between `from` and `to`,
+    /// not counting `from` itself.
+    fn is_clear(&self, from: usize, to: usize) -> bool {
+        let (lo, hi) = if from < to {
+            (from + 1, to)
+        } else {
+            (to, from - 1)
+        };
+        (lo..=hi).all(|h| self.cells[h] == EMPTY)
+    }
+
+    /// Returns the amphipod that can leave a room and how deep it is.
+    fn leaving(&self, room: usize) -> Option<(usize, u8)> {
+        if self.is_settled(room) {
+            return None;
+        }
+        self.room(room)
+            .iter()
+            .enumerate()
+            .find(|&(_, &a)| a != EMPTY)
+            .map(|(d, &a)| (d, a))
+    }
+
+    /// Returns the depth an amphipod would move to in its room if it can
+    /// enter.
+    fn entering(&self, room: usize) -> Option<usize> {
+        if !self.is_settled(room) {
+            return None;
+        }
+        self.room(room).iter().rposition(|&a| a == EMPTY)
+    }
+
+    fn moved(&self, from: usize, to: usize) -> Self {
+        let mut next = *self;
+        next.cells[to] = self.cells[from];
+        next.cells[from] = EMPTY;
+        next
+    }
+
+    /// Returns the burrows reachable with a single move and the energy used.
+    fn moves(&self) -> Vec<(Self, usize)> {
+        let mut moves = vec![];
+
+        // Amphipods in the hallway can only move into their own room.
+        for h in 0..HALLWAY {
+            let a = self.cells[h];
+            if a == EMPTY {
+                continue;
+            }
+            let room = a as usize - 1;
+            let e = entrance(room);
+            if let Some(d) = self.entering(room) {
+                if self.is_clear(h, e) {
+                    let steps = h.abs_diff(e) + d + 1;
+                    moves.push((self.moved(h, Self::slot(room, d)), steps * ENERGY[room]));
+                }
+            }
+        }
+
+        for room in 0..ROOMS {
+            let (d, a) = match self.leaving(room) {
+                Some(leaving) => leaving,
+                None => continue,
+            };
+            let from = Self::slot(room, d);
+            let e = entrance(room);
+            let energy = ENERGY[a as usize - 1];
+
+            // Straight into its own room.
+            let target = a as usize - 1;
+            if let Some(td) = self.entering(target) {
+                let te = entrance(target);
+                if self.is_clear(e, te) && self.cells[e] == EMPTY {
+                    let steps = d + 1 + e.abs_diff(te) + td + 1;
+                    moves.push((self.moved(from, Self::slot(target, td)), steps * energy));
+                }
+            }
+
+            // Or stop somewhere in the hallway.
+            for h in 0..HALLWAY {
+                if (0..ROOMS).any(|r| entrance(r) == h) {
+                    continue;
+                }
+                if self.cells[h] == EMPTY && self.is_clear(e, h) {
+                    let steps = d + 1 + e.abs_diff(h);
+                    moves.push((self.moved(from, h), steps * energy));
+                }
+            }
+        }
+
+        moves
+    }
+
+    /// Lower bound on the energy needed to organize the burrow. Assumes every
+    /// amphipod outside its room can walk straight into the top of its room.
+    fn estimate(&self) -> usize {
+        let mut energy = 0;
+        for h in 0..HALLWAY {
+            let a = self.cells[h];
+            if a != EMPTY {
+                let room = a as usize - 1;
+                energy += (h.abs_diff(entrance(room)) + 1) * ENERGY[room];
+            }
+        }
+        for room in 0..ROOMS {
+            for (d, &a) in self.room(room).iter().enumerate() {
+                if a != EMPTY && a as usize != room + 1 {
+                    let target = a as usize - 1;
+                    let steps = d + 1 + entrance(room).abs_diff(entrance(target)) + 1;
+                    energy += steps * ENERGY[target];
+                }
+            }
+        }
+        energy
+    }
+}
+
+impl fmt::Display for Burrow {
+    /// Formats the value using the given formatter.
+    ///
+    /// * `f`     - Formatter
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = |a: u8| {
+            if a == EMPTY {
+                '.'
+            } else {
+                (b'A' + a - 1) as char
+            }
+        };
+
+        writeln!(f, "#############")?;
+        write!(f, "#")?;
+        for h in 0..HALLWAY {
+            write!(f, "{}", c(self.cells[h]))?;
+        }
+        writeln!(f, "#")?;
+        for d in 0..self.depth {
+            write!(f, "{}", if d == 0 { "###" } else { "  #" })?;
+            for room in 0..ROOMS {
+                write!(f, "{}#", c(self.cells[Self::slot(room, d)]))?;
+            }
+            writeln!(f, "{}", if d == 0 { "##" } else { "" })?;
+        }
+        write!(f, "  #########")
+    }
+}
+
+fn read(input_file: &str) -> Vec<String> {
+    let contents = fs::read_to_string(input_file).expect("Something went wrong reading the file");
+    contents.lines().map(String::from).collect()
+}
+
+fn organize(burrow: Burrow) -> usize {
+    let (energy, _path) = search::a_star(
+        burrow,
+        |b: &Burrow| b.is_organized(),
+        |b: &Burrow| b.moves(),
+        |b: &Burrow| b.estimate(),
+    )
+    .expect("amphipods cannot be organized");
+
+    energy
+}
+
+pub fn part1(input_file: &str) -> String {
+    let rows = read(input_file);
+    let rows: Vec<&str> = rows.iter().map(|s| s.as_str()).collect();
+    organize(Burrow::parse(&rows)).to_string()
+}
+
+pub fn part2(input_file: &str) -> String {
+    let rows = read(input_file);
+    let mut rows: Vec<&str> = rows.iter().map(|s| s.as_str()).collect();
+
+    // Unfold the diagram between the first and last room rows.
+    for (i, row) in UNFOLDED.iter().enumerate() {
+        rows.insert(3 + i, row);
+    }
+    organize(Burrow::parse(&rows)).to_string()
+}
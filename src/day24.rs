@@ -0,0 +1,323 @@
+use std::fs;
+
+use itertools::Itertools;
+
+/// Number of instructions MONAD uses to check each digit.
+const BLOCK_LEN: usize = 18;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Operand {
+    Register(usize),
+    Number(i64),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Instruction {
+    Inp(usize),
+    Add(usize, Operand),
+    Mul(usize, Operand),
+    Div(usize, Operand),
+    Mod(usize, Operand),
+    Eql(usize, Operand),
+}
+
+/// Index of register `w`, `x`, `y` or `z`.
+///
+/// * `s` - The register name.
+fn register(s: &str) -> usize {
+    match s {
+        "w" => 0,
+        "x" => 1,
+        "y" => 2,
+        "z" => 3,
+        _ => panic!("invalid register '{}'", s),
+    }
+}
+
+impl Operand {
+    fn parse(s: &str) -> Self {
+        match s.parse() {
+            Ok(n) => Self::Number(n),
+            Err(_) => Self::Register(register(s)),
+        }
+    }
+}
+
+impl Instruction {
+    fn parse(line: &str) -> Self {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens[..] {
+            ["inp", a] => Self::Inp(register(a)),
+            ["add", a, b] => Self::Add(register(a), Operand::parse(b)),
+            ["mul", a, b] => Self::Mul(register(a), Operand::parse(b)),
+            ["div", a, b] => Self::Div(register(a), Operand::parse(b)),
+            ["mod", a, b] => Self::Mod(register(a), Operand::parse(b)),
+            ["eql", a, b] => Self::Eql(register(a), Operand::parse(b)),
+            _ => panic!("invalid instruction '{}'", line),
+        }
+    }
+}
+
+struct Alu {
+    registers: [i64; 4],
+}
+
+impl Alu {
+    fn new() -> Self {
+        Self { registers: [0; 4] }
+    }
+
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(r) => self.registers[r],
+            Operand::Number(n) => n,
+        }
+    }
+
+    /// Runs the program and returns the final value of `z` or `None` if the
+    /// ALU crashes on a division by zero, an invalid modulo or missing input.
+    ///
+    /// * `program` - The instructions.
+    /// * `input`   - Values read by `inp` instructions in order.
+    fn run(&mut self, program: &[Instruction], input: &[i64]) -> Option<i64> {
+        let mut input = input.iter();
+        for &instruction in program {
+            match instruction {
+                Instruction::Inp(a) => self.registers[a] = *input.next()?,
+                Instruction::Add(a, b) => self.registers[a] += self.value(b),
+                Instruction::Mul(a, b) => self.registers[a] *= self.value(b),
+                Instruction::Div(a, b) => {
+                    let b = self.value(b);
+                    if b == 0 {
+                        return None;
+                    }
+                    self.registers[a] /= b;
+                }
+                Instruction::Mod(a, b) => {
+                    let b = self.value(b);
+                    if self.registers[a] < 0 || b <= 0 {
+                        return None;
+                    }
+                    self.registers[a] %= b;
+                }
+                Instruction::Eql(a, b) => {
+                    self.registers[a] = (self.registers[a] == self.value(b)) as i64;
+                }
+            }
+        }
+        Some(self.registers[3])
+    }
+}
+
+/// The constants that differ between the blocks MONAD runs for each digit.
+///
+/// Each block treats `z` as a stack of base 26 digits. It compares the input
+/// with the top of the stack plus `check`. Blocks that divide `z` by 26 pop
+/// the stack. If the comparison fails the input plus `offset` is pushed.
+#[derive(Copy, Clone, Debug)]
+struct Block {
+    div: i64,
+    check: i64,
+    offset: i64,
+}
+
+/// Splits MONAD into its per-digit blocks.
+///
+/// * `program` - The instructions.
+fn blocks(program: &[Instruction]) -> Vec<Block> {
+    assert!(
+        !program.is_empty() && program.len().is_multiple_of(BLOCK_LEN),
+        "expecting blocks of {} instructions",
+        BLOCK_LEN
+    );
+
+    let template = &program[..BLOCK_LEN];
+    program
+        .chunks(BLOCK_LEN)
+        .enumerate()
+        .map(|(i, chunk)| {
+            // Apart from the constants every block is the same.
+            let same = (0..BLOCK_LEN)
+                .filter(|k| ![4, 5, 15].contains(k))
+                .all(|k| chunk[k] == template[k]);
+            assert!(same, "block {} differs from the first block", i);
+
+            match (chunk[0], chunk[4], chunk[5], chunk[15]) {
+                (
+                    Instruction::Inp(0),
+                    Instruction::Div(3, Operand::Number(div)),
+                    Instruction::Add(1, Operand::Number(check)),
+                    Instruction::Add(2, Operand::Number(offset)),
+                ) => Block { div, check, offset },
+                _ => panic!("unexpected instructions in block {}", i),
+            }
+        })
+        .collect()
+}
+
+/// Pairs up the blocks that push and pop the same stack entry. A number is
+/// valid when `digits[j] == digits[i] + d` for every returned `(i, j, d)`.
+///
+/// * `blocks` - The blocks.
+fn constraints(blocks: &[Block]) -> Vec<(usize, usize, i64)> {
+    let mut stack: Vec<(usize, i64)> = vec![];
+    let mut constraints = vec![];
+
+    for (j, block) in blocks.iter().enumerate() {
+        match block.div {
+            1 => {
+                // No digit can match so this block always pushes.
+                assert!(block.check > 9, "block {} may not push", j);
+                stack.push((j, block.offset));
+            }
+            26 => {
+                let (i, offset) = stack.pop().expect("stack underflow");
+                constraints.push((i, j, offset + block.check));
+            }
+            div => panic!("unexpected division by {} in block {}", div, j),
+        }
+    }
+
+    assert!(stack.is_empty(), "z cannot reach 0");
+    constraints
+}
+
+/// Returns the largest or smallest digits accepted by the blocks or `None`
+/// if no number is valid.
+///
+/// * `blocks`  - The blocks.
+/// * `largest` - Whether to find the largest or the smallest number.
+fn model_number(blocks: &[Block], largest: bool) -> Option<Vec<i64>> {
+    let mut digits = vec![0; blocks.len()];
+    for (i, j, d) in constraints(blocks) {
+        let a = if largest { 9.min(9 - d) } else { 1.max(1 - d) };
+        if !(1..=9).contains(&a) || !(1..=9).contains(&(a + d)) {
+            return None;
+        }
+        digits[i] = a;
+        digits[j] = a + d;
+    }
+    Some(digits)
+}
+
+fn read(input_file: &str) -> Vec<Instruction> {
+    let contents = fs::read_to_string(input_file).expect("Something went wrong reading the file");
+    contents.lines().map(Instruction::parse).collect()
+}
+
+fn solve(input_file: &str, largest: bool) -> String {
+    let program = read(input_file);
+    let blocks = blocks(&program);
+
+    let digits = model_number(&blocks, largest).expect("no valid model number");
+    assert_eq!(
+        Alu::new().run(&program, &digits),
+        Some(0),
+        "MONAD rejected the model number"
+    );
+
+    digits.iter().join("")
+}
+
+pub fn part1(input_file: &str) -> String {
+    solve(input_file, true)
+}
+
+pub fn part2(input_file: &str) -> String {
+    solve(input_file, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Largest block span that is cross-checked by brute force.
+    const MAX_BRUTE_FORCE_DIGITS: usize = 4;
+
+    /// Tries every number in order and returns the first one the program accepts.
+    ///
+    /// * `program` - The instructions.
+    /// * `n`       - Number of digits.
+    /// * `largest` - Whether to find the largest or the smallest number.
+    fn brute_force(program: &[Instruction], n: usize, largest: bool) -> Option<Vec<i64>> {
+        let digits: Vec<i64> = if largest {
+            (1..=9).rev().collect()
+        } else {
+            (1..=9).collect()
+        };
+
+        (0..n)
+            .map(|_| digits.iter().copied())
+            .multi_cartesian_product()
+            .find(|input| Alu::new().run(program, input) == Some(0))
+    }
+
+    /// Compares the block analysis with brute force on the reduced programs
+    /// formed by each short pair of matching blocks and the blocks between them.
+    ///
+    /// * `program` - The instructions.
+    /// * `blocks`  - The blocks.
+    /// * `largest` - Whether to find the largest or the smallest number.
+    fn cross_check(program: &[Instruction], blocks: &[Block], largest: bool) {
+        for (i, j, _) in constraints(blocks) {
+            if j - i + 1 > MAX_BRUTE_FORCE_DIGITS {
+                continue;
+            }
+            let reduced = &program[i * BLOCK_LEN..(j + 1) * BLOCK_LEN];
+            assert_eq!(
+                model_number(&blocks[i..=j], largest),
+                brute_force(reduced, j - i + 1, largest),
+                "block analysis disagrees with brute force for blocks {}..={}",
+                i,
+                j
+            );
+        }
+    }
+
+    /// Runs the program text and returns the registers `w`, `x`, `y` and `z`.
+    fn run(source: &str, input: &[i64]) -> Option<[i64; 4]> {
+        let program: Vec<Instruction> = source.lines().map(Instruction::parse).collect();
+        let mut alu = Alu::new();
+        alu.run(&program, input)?;
+        Some(alu.registers)
+    }
+
+    #[test]
+    fn alu() {
+        // Negates the input.
+        assert_eq!(run("inp x\nmul x -1", &[7]), Some([0, -7, 0, 0]));
+        // Checks whether the second input is three times the first.
+        let triple = "inp z\ninp x\nmul z 3\neql z x";
+        assert_eq!(run(triple, &[2, 6]), Some([0, 6, 0, 1]));
+        assert_eq!(run(triple, &[2, 5]), Some([0, 5, 0, 0]));
+        // Stores the lowest four bits of the input in w, x, y and z.
+        let bits = "inp w\nadd z w\nmod z 2\ndiv w 2\nadd y w\nmod y 2\ndiv w 2\n\
+                    add x w\nmod x 2\ndiv w 2\nmod w 2";
+        assert_eq!(run(bits, &[13]), Some([1, 1, 0, 1]));
+        // Division truncates towards zero.
+        assert_eq!(run("inp w\ndiv w 2", &[-7]), Some([-3, 0, 0, 0]));
+    }
+
+    #[test]
+    fn alu_crashes() {
+        assert_eq!(run("inp w\ninp x", &[1]), None);
+        assert_eq!(run("inp w\ndiv w x", &[1]), None);
+        assert_eq!(run("inp w\nmod w 2", &[-1]), None);
+        assert_eq!(run("inp w\nmod w x", &[1]), None);
+    }
+
+    #[test]
+    fn block_analysis_matches_brute_force() {
+        let program = read("./inputs/day24/0.txt");
+        let blocks = blocks(&program);
+        for largest in [true, false] {
+            cross_check(&program, &blocks, largest);
+        }
+    }
+
+    #[test]
+    fn example() {
+        assert_eq!(part1("./inputs/day24/0.txt"), "93968997399993");
+        assert_eq!(part2("./inputs/day24/0.txt"), "71411271171131");
+    }
+}
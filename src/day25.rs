@@ -0,0 +1,131 @@
+use std::fmt;
+use std::fs;
+
+use crate::simulation::Simulation;
+
+const EMPTY: u8 = b'.';
+const EAST: u8 = b'>';
+const SOUTH: u8 = b'v';
+
+fn read(input_file: &str) -> Vec<Vec<u8>> {
+    let contents = fs::read_to_string(input_file).expect("Something went wrong reading the file");
+    contents
+        .lines()
+        .map(|row| {
+            row.bytes()
+                .inspect(|&c| {
+                    assert!(
+                        [EMPTY, EAST, SOUTH].contains(&c),
+                        "invalid location '{}'",
+                        c as char
+                    )
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+pub struct Seafloor {
+    grid: Vec<Vec<u8>>,
+    step: usize,
+    last_moves: usize,
+}
+
+impl fmt::Display for Seafloor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.grid.iter() {
+            writeln!(f, "{}", String::from_utf8_lossy(row))?;
+        }
+        write!(f, "step {}: {} moved", self.step, self.last_moves)
+    }
+}
+
+pub struct SeaCucumbers {
+    current: Seafloor,
+    next: Vec<Vec<u8>>,
+}
+
+impl SeaCucumbers {
+    pub fn new(input_file: &str) -> Self {
+        let grid = read(input_file);
+
+        let h = grid.len();
+        assert!(h > 0);
+
+        let w = grid[0].len();
+        assert!(w > 0);
+
+        Self {
+            next: grid.clone(),
+            current: Seafloor {
+                grid,
+                step: 0,
+                last_moves: 0,
+            },
+        }
+    }
+
+    /// Moves every sea cucumber in a herd that faces an empty location. The
+    /// grid wraps around so cucumbers leaving one edge enter the opposite one.
+    /// All cucumbers in the herd look before any of them move.
+    ///
+    /// * `herd` - The herd to move.
+    /// * `di`   - Rows moved down by the herd.
+    /// * `dj`   - Columns moved right by the herd.
+    fn move_herd(&mut self, herd: u8, di: usize, dj: usize) -> usize {
+        let current = &mut self.current.grid;
+        let next = &mut self.next;
+
+        let h = current.len();
+        let w = current[0].len();
+
+        for (row, next_row) in current.iter().zip(next.iter_mut()) {
+            next_row.copy_from_slice(row);
+        }
+
+        let mut moves = 0;
+        for i in 0..h {
+            for j in 0..w {
+                let (m, n) = ((i + di) % h, (j + dj) % w);
+                if current[i][j] == herd && current[m][n] == EMPTY {
+                    next[i][j] = EMPTY;
+                    next[m][n] = herd;
+                    moves += 1;
+                }
+            }
+        }
+
+        std::mem::swap(current, next);
+        moves
+    }
+}
+
+impl Simulation for SeaCucumbers {
+    type State = Seafloor;
+
+    fn step(&mut self) {
+        let moves = self.move_herd(EAST, 0, 1) + self.move_herd(SOUTH, 1, 0);
+        self.current.step += 1;
+        self.current.last_moves = moves;
+    }
+
+    fn state(&self) -> &Seafloor {
+        &self.current
+    }
+
+    fn restore(&mut self, snapshot: Seafloor) {
+        self.current = snapshot;
+    }
+}
+
+pub fn part1(input_file: &str) -> String {
+    let mut sea_cucumbers = SeaCucumbers::new(input_file);
+    sea_cucumbers.run_until(|seafloor| seafloor.step > 0 && seafloor.last_moves == 0);
+    sea_cucumbers.state().step.to_string()
+}
+
+pub fn part2(_input_file: &str) -> String {
+    // Day 25 only has one puzzle.
+    "Merry Christmas!".to_string()
+}
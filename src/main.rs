@@ -1,121 +1,173 @@
 use std::env;
+use std::path::Path;
+use std::time::Duration;
 
-extern crate itertools;
-#[macro_use]
-extern crate lazy_static;
-extern crate regex;
-
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
+#[cfg(feature = "day01")]
+use aoc_2021::day01;
+use aoc_2021::repl;
+use aoc_2021::solutions::{self, Solution};
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() != 2 {
-        eprintln!("Please specify day [1-31]");
-        return;
-    }
+mod report;
+mod watch;
 
-    let day = args[1].parse::<u8>();
+fn parse_day(s: &str) -> Option<u8> {
+    let day = s.parse::<u8>();
     if day.is_err() {
         eprintln!(
             "Invalid day '{}'. Please specify a value between 1 and 31.",
-            args[1]
+            s
         );
-        return;
+        return None;
     }
-    let day = day.ok().unwrap();
+    day.ok()
+}
 
-    match day {
-        1 => {
-            day01::part1("./inputs/day01/1.txt");
-            day01::part2("./inputs/day01/1.txt");
-        }
-        2 => {
-            day02::part1("./inputs/day02/1.txt");
-            day02::part2("./inputs/day02/1.txt");
-        }
-        3 => {
-            day03::part1("./inputs/day03/1.txt");
-            day03::part2("./inputs/day03/1.txt");
-        }
-        4 => {
-            day04::part1("./inputs/day04/1.txt");
-            day04::part2("./inputs/day04/1.txt");
-        }
-        5 => {
-            day05::part1("./inputs/day05/1.txt");
-            day05::part2("./inputs/day05/1.txt");
-        }
-        6 => {
-            day06::part1("./inputs/day06/1.txt");
-            day06::part2("./inputs/day06/1.txt");
-        }
-        7 => {
-            day07::part1("./inputs/day07/1.txt");
-            day07::part2("./inputs/day07/1.txt");
-        }
-        8 => {
-            day08::part1("./inputs/day08/1.txt");
-            day08::part2("./inputs/day08/1.txt");
-        }
-        9 => {
-            day09::part1("./inputs/day09/1.txt");
-            day09::part2("./inputs/day09/1.txt");
-        }
-        10 => {
-            day10::part_1_and_2("./inputs/day10/1.txt");
-        }
-        11 => {
-            day11::part1("./inputs/day11/1.txt");
-            day11::part2("./inputs/day11/1.txt");
-        }
-        12 => {
-            day12::part1("./inputs/day12/3.txt");
-            day12::part2("./inputs/day12/3.txt");
-        }
-        13 => {
-            day13::part_1_and_2("./inputs/day13/1.txt");
-        }
-        14 => {
-            day14::part1("./inputs/day14/1.txt");
-            day14::part2("./inputs/day14/1.txt");
-        }
-        15 => {
-            day15::part1("./inputs/day15/1.txt");
-            day15::part2("./inputs/day15/1.txt");
-        }
-        16 => {
-            day16::part1("./inputs/day16/1.txt");
-            day16::part2("./inputs/day16/1.txt");
-        }
-        17 => {
-            day17::part1("./inputs/day17/1.txt");
-            day17::part2("./inputs/day17/1.txt");
+/// Removes `--timeout <seconds>` from the arguments and returns the time
+/// budget for each part. A timeout of 0 waits indefinitely.
+fn parse_timeout(args: &mut Vec<&str>) -> Result<Option<Duration>, String> {
+    let i = match args.iter().position(|&a| a == "--timeout") {
+        Some(i) => i,
+        None => return Ok(Some(solutions::DEFAULT_TIMEOUT)),
+    };
+    if i + 1 >= args.len() {
+        return Err("Please specify --timeout in seconds".to_string());
+    }
+
+    let secs = args[i + 1]
+        .parse::<u64>()
+        .map_err(|_| format!("Invalid timeout '{}'", args[i + 1]))?;
+    args.drain(i..=i + 1);
+
+    match secs {
+        0 => Ok(None),
+        s => Ok(Some(Duration::from_secs(s))),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<&str> = args.iter().skip(1).map(|s| s.as_str()).collect();
+
+    let budget = match parse_timeout(&mut args) {
+        Ok(budget) => budget,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
         }
-        d if d <= 31 => {
-            println!("Day {} not done yet", day);
+    };
+
+    match args[..] {
+        ["all"] => {
+            for solution in solutions::SOLUTIONS {
+                run(solution, solution.input_file, budget);
+            }
+        }
+        ["report"] => report::run(None, budget),
+        ["report", output_file] => report::run(Some(output_file), budget),
+        ["repl", day] => {
+            if let Some(day) = parse_day(day) {
+                match solutions::find(day) {
+                    Some(solution) => repl::run(day, solution.input_file),
+                    None => repl::run(day, &format!("./inputs/day{:02}/1.txt", day)),
+                }
+            }
+        }
+        ["repl", day, input_file] => {
+            if let Some(day) = parse_day(day) {
+                repl::run(day, input_file);
+            }
+        }
+        #[cfg(feature = "day01")]
+        ["depths", ref rest @ ..] if rest.len() <= 3 => depth_report(rest),
+        ["watch", day] => {
+            if let Some(day) = parse_day(day) {
+                watch::run(day, budget);
+            }
+        }
+        [day] | [day, _] => {
+            if let Some(day) = parse_day(day) {
+                match solutions::find(day) {
+                    Some(solution) => {
+                        let input_file = args.get(1).copied().unwrap_or(solution.input_file);
+                        run(solution, input_file, budget)
+                    }
+                    None if day <= 31 => println!("Day {} not done yet or not compiled in", day),
+                    None => eprintln!(
+                        "Invalid day '{}'. Please specify a value between 1 and 31.",
+                        day
+                    ),
+                }
+            }
+        }
+        _ => {
+            eprintln!("Please specify day [1-31] [input_file] [--timeout <seconds>]");
+            eprintln!("       all [--timeout <seconds>]");
+            eprintln!("       repl <day> [input_file]");
+            eprintln!("       report [output_file] [--timeout <seconds>]");
+            eprintln!("       watch <day> [--timeout <seconds>]");
+            #[cfg(feature = "day01")]
+            eprintln!("       depths [input_file] [window] [std_devs]");
         }
-        d => {
-            eprintln!(
-                "Invalid day '{}'. Please specify a value between 1 and 31.",
-                d
-            );
+    }
+}
+
+/// Prints the day 1 depth profile.
+///
+/// * `args` - Optional input file, window size and outlier threshold.
+#[cfg(feature = "day01")]
+fn depth_report(args: &[&str]) {
+    let input_file = args
+        .first()
+        .copied()
+        .or_else(|| solutions::find(1).map(|s| s.input_file))
+        .unwrap_or("./inputs/day01/1.txt");
+
+    let window = match args.get(1).map(|s| s.parse::<usize>()) {
+        None => day01::DEFAULT_WINDOW,
+        Some(Ok(window)) if window > 0 => window,
+        _ => {
+            eprintln!("Invalid window '{}'", args[1]);
+            return;
+        }
+    };
+
+    let threshold = match args.get(2).map(|s| s.parse::<f64>()) {
+        None => day01::DEFAULT_THRESHOLD,
+        Some(Ok(threshold)) if threshold >= 0.0 => threshold,
+        _ => {
+            eprintln!("Invalid number of standard deviations '{}'", args[2]);
+            return;
+        }
+    };
+
+    println!(
+        "{}",
+        day01::DepthReport::read(input_file, window, threshold)
+    );
+}
+
+fn run(solution: &Solution, input_file: &str, budget: Option<Duration>) {
+    if !Path::new(input_file).exists() {
+        println!("day {:02}: input missing ({})", solution.day, input_file);
+        return;
+    }
+
+    for i in 0..solution.parts.len() {
+        let label = format!("day {:02}: part {} = ", solution.day, i + 1);
+        let result = solutions::run_part(solution, i, input_file, budget);
+
+        // Line up answers drawn across multiple lines.
+        let indent = format!("\n{}", " ".repeat(label.len()));
+        let answer = result.answer.to_string();
+        match result.memory {
+            Some(memory) => println!(
+                "{}{} ({:.2?}, {})",
+                label,
+                answer.replace('\n', &indent),
+                result.elapsed,
+                memory
+            ),
+            None => println!("{}{}", label, answer.replace('\n', &indent)),
         }
     }
 }
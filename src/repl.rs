@@ -0,0 +1,174 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use crate::simulation::Simulation;
+
+/// Puzzle state that can be inspected and manipulated one command at a time.
+pub trait Repl {
+    /// Returns the usage lines for the day specific commands.
+    fn help(&self) -> Vec<&'static str>;
+
+    /// Executes a command and returns the text to display.
+    ///
+    /// * `command` - The command name.
+    /// * `args`    - The remaining whitespace separated arguments.
+    fn eval(&mut self, command: &str, args: &[&str]) -> Result<String, String>;
+}
+
+/// Command handler for any simulation whose state can be displayed.
+pub struct SimulationRepl<S: Simulation> {
+    simulation: S,
+    saved: Option<S::State>,
+}
+
+impl<S: Simulation> SimulationRepl<S> {
+    pub fn new(simulation: S) -> Self {
+        Self {
+            simulation,
+            saved: None,
+        }
+    }
+}
+
+impl<S> Repl for SimulationRepl<S>
+where
+    S: Simulation,
+    S::State: fmt::Display,
+{
+    fn help(&self) -> Vec<&'static str> {
+        vec![
+            "step [n]  advance the simulation by n steps (default 1)",
+            "show      show the current state",
+            "save      take a snapshot of the current state",
+            "restore   go back to the last snapshot",
+        ]
+    }
+
+    fn eval(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match (command, args) {
+            ("step", []) => {
+                self.simulation.step();
+                Ok(format!("{}", self.simulation.state()))
+            }
+            ("step", [n]) => {
+                let n = n
+                    .parse::<usize>()
+                    .map_err(|_| format!("invalid step count '{}'", n))?;
+                for _ in 0..n {
+                    self.simulation.step();
+                }
+                Ok(format!("{}", self.simulation.state()))
+            }
+            ("show", []) => Ok(format!("{}", self.simulation.state())),
+            ("save", []) => {
+                self.saved = Some(self.simulation.snapshot());
+                Ok("saved".to_string())
+            }
+            ("restore", []) => {
+                let snapshot = self.saved.clone().ok_or("no snapshot saved")?;
+                self.simulation.restore(snapshot);
+                Ok(format!("{}", self.simulation.state()))
+            }
+            _ => Err(format!("unknown command '{}'", command)),
+        }
+    }
+}
+
+/// Returns the command handler for a day or `None` if the day does not
+/// support interactive mode.
+///
+/// * `day`        - The day.
+/// * `input_file` - The input file used to build the puzzle state.
+// The input file is only used by the days with handlers.
+#[cfg_attr(
+    not(any(
+        feature = "day02",
+        feature = "day03",
+        feature = "day04",
+        feature = "day06",
+        feature = "day11",
+        feature = "day12",
+        feature = "day13",
+        feature = "day16"
+    )),
+    allow(unused_variables)
+)]
+fn handler(day: u8, input_file: &str) -> Option<Box<dyn Repl>> {
+    match day {
+        #[cfg(feature = "day02")]
+        2 => Some(Box::new(crate::day02::CourseRepl::new(input_file))),
+        #[cfg(feature = "day03")]
+        3 => Some(Box::new(crate::day03::DiagnosticsRepl::new(input_file))),
+        #[cfg(feature = "day04")]
+        4 => Some(Box::new(crate::day04::BingoRepl::new(input_file))),
+        #[cfg(feature = "day06")]
+        6 => Some(Box::new(SimulationRepl::new(crate::day06::Growth::new(
+            input_file,
+        )))),
+        #[cfg(feature = "day11")]
+        11 => Some(Box::new(SimulationRepl::new(crate::day11::Octopuses::new(
+            input_file,
+        )))),
+        #[cfg(feature = "day12")]
+        12 => Some(Box::new(crate::day12::GraphRepl::new(input_file))),
+        #[cfg(feature = "day13")]
+        13 => Some(Box::new(crate::day13::OrigamiRepl::new(input_file))),
+        #[cfg(feature = "day16")]
+        16 => Some(Box::new(crate::day16::PacketRepl::new(input_file))),
+        _ => None,
+    }
+}
+
+/// Loads the input for a day and reads commands from stdin until `quit` or
+/// end of input.
+///
+/// * `day`        - The day.
+/// * `input_file` - The input file used to build the puzzle state.
+pub fn run(day: u8, input_file: &str) {
+    let mut repl = match handler(day, input_file) {
+        Some(repl) => repl,
+        None => {
+            eprintln!("Day {} does not support interactive mode", day);
+            return;
+        }
+    };
+
+    println!("day {:02}: loaded {}", day, input_file);
+    println!("Type 'help' for a list of commands.");
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("day {:02}> ", day);
+        io::stdout().flush().expect("Unable to flush stdout");
+
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(e)) => {
+                eprintln!("{}", e);
+                break;
+            }
+            None => {
+                println!();
+                break;
+            }
+        };
+
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.first() {
+            None => continue,
+            Some(&"quit") | Some(&"exit") => break,
+            Some(&"help") => {
+                for usage in repl.help() {
+                    println!("  {}", usage);
+                }
+                println!("  help");
+                println!("  quit");
+            }
+            Some(command) => match repl.eval(command, &words[1..]) {
+                Ok(output) => println!("{}", output),
+                Err(e) => eprintln!("error: {}", e),
+            },
+        }
+    }
+}
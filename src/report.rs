@@ -0,0 +1,191 @@
+use std::fmt::Write;
+use std::fs;
+
+use std::time::Duration;
+
+use aoc_2021::alloc_stats;
+use aoc_2021::solutions::{self, PartResult, Solution};
+use aoc_2021::watchdog::Outcome;
+
+/// Puzzle details recorded in `puzzles/dayNN.md`.
+struct Puzzle {
+    title: String,
+    answers: Vec<String>,
+}
+
+impl Puzzle {
+    /// Reads the title and submitted answers from the puzzle description.
+    /// Returns `None` if the description has not been captured.
+    ///
+    /// * `day` - The day.
+    fn read(day: u8) -> Option<Self> {
+        let contents = fs::read_to_string(format!("./puzzles/day{:02}.md", day)).ok()?;
+
+        // First line is formatted as `### --- Day 1: Sonar Sweep ---`.
+        let title = contents
+            .lines()
+            .next()
+            .and_then(|line| line.split_once(": "))
+            .map(|(_, title)| title.trim_end_matches(" ---").to_string())
+            .unwrap_or_default();
+
+        let answers = contents
+            .lines()
+            .filter_map(|line| line.strip_prefix("Your puzzle answer was `"))
+            .filter_map(|line| line.split('`').next())
+            .map(String::from)
+            .collect();
+
+        Some(Self { title, answers })
+    }
+}
+
+/// Describes whether an answer matches the submitted one.
+///
+/// * `answer`   - The computed answer.
+/// * `recorded` - The submitted answer if there is one.
+fn check(answer: &Outcome<String>, recorded: Option<&String>) -> &'static str {
+    let answer = match answer {
+        Outcome::Done(answer) => answer,
+        _ => return "**no**",
+    };
+    match recorded {
+        // Answers drawn as ASCII art can't be compared with the submitted text.
+        Some(_) if answer.contains('\n') => "n/a",
+        Some(recorded) if recorded == answer => "yes",
+        Some(_) => "**no**",
+        None => "n/a",
+    }
+}
+
+/// Formats an answer for a table cell.
+///
+/// * `answer` - The computed answer.
+fn cell(answer: &Outcome<String>) -> String {
+    match answer {
+        Outcome::Done(answer) if answer.contains('\n') => "see below".to_string(),
+        Outcome::Done(answer) => format!("`{}`", answer),
+        _ => format!("**{}**", answer),
+    }
+}
+
+/// Runs a solution and appends its table row to the report. Answers that
+/// span multiple lines are appended to `drawings`.
+///
+/// * `report`   - The report.
+/// * `drawings` - Multi-line answers listed after the table.
+/// * `solution` - The solution to run.
+/// * `budget`   - Time budget for each part.
+fn write_row(
+    report: &mut String,
+    drawings: &mut String,
+    solution: &Solution,
+    budget: Option<Duration>,
+) -> std::fmt::Result {
+    let puzzle = Puzzle::read(solution.day);
+    let title = puzzle.as_ref().map_or("", |p| p.title.as_str());
+    let recorded = |i: usize| puzzle.as_ref().and_then(|p| p.answers.get(i));
+
+    // Real inputs aren't always available so don't start solvers without one.
+    if fs::metadata(solution.input_file).is_err() {
+        write!(report, "| {} | {} ", solution.day, title)?;
+        for _ in solution.parts.iter() {
+            write!(report, "| input missing | - ")?;
+        }
+        write!(report, "| missing | n/a / n/a")?;
+        if alloc_stats::enabled() {
+            write!(report, " | - | -")?;
+        }
+        writeln!(report, " |")?;
+        return Ok(());
+    }
+
+    let input = fs::read_to_string(solution.input_file).unwrap_or_default();
+    let results: Vec<PartResult> = (0..solution.parts.len())
+        .map(|i| solutions::run_part(solution, i, solution.input_file, budget))
+        .collect();
+
+    write!(report, "| {} | {} ", solution.day, title)?;
+    for result in results.iter() {
+        write!(
+            report,
+            "| {} | {:.2?} ",
+            cell(&result.answer),
+            result.elapsed
+        )?;
+    }
+    write!(
+        report,
+        "| {} lines, {} bytes ",
+        input.lines().count(),
+        input.len()
+    )?;
+    for (i, result) in results.iter().enumerate() {
+        let sep = if i == 0 { "| " } else { " / " };
+        write!(report, "{}{}", sep, check(&result.answer, recorded(i)))?;
+    }
+    if alloc_stats::enabled() {
+        for result in results.iter() {
+            match result.memory {
+                Some(memory) => write!(report, " | {}", memory)?,
+                None => write!(report, " | -")?,
+            }
+        }
+    }
+    writeln!(report, " |")?;
+
+    for (i, result) in results.iter().enumerate() {
+        if let Outcome::Done(answer) = &result.answer {
+            if answer.contains('\n') {
+                writeln!(drawings, "\n### Day {} part {}\n", solution.day, i + 1)?;
+                writeln!(drawings, "```\n{}\n```", answer)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Runs all solutions and returns a Markdown summary.
+///
+/// * `budget` - Time budget for each part.
+fn generate(budget: Option<Duration>) -> String {
+    let mut report = String::new();
+    let mut drawings = String::new();
+
+    report.push_str("# Advent of Code 2021\n\n");
+    report.push_str("| Day | Title | Part 1 | Time | Part 2 | Time | Input | Matches submitted |");
+    if alloc_stats::enabled() {
+        report.push_str(" Part 1 memory | Part 2 memory |");
+    }
+    report
+        .push_str("\n| --: | ----- | ------ | ---: | ------ | ---: | ----: | :---------------: |");
+    if alloc_stats::enabled() {
+        report.push_str(" ------------- | ------------- |");
+    }
+    report.push('\n');
+
+    for solution in solutions::SOLUTIONS {
+        write_row(&mut report, &mut drawings, solution, budget).expect("Unable to write report");
+    }
+
+    if !drawings.is_empty() {
+        report.push_str("\n## Drawings\n");
+        report.push_str(&drawings);
+    }
+    report
+}
+
+/// Writes the report to a file or stdout.
+///
+/// * `output_file` - The output file or `None` for stdout.
+/// * `budget`      - Time budget for each part.
+pub fn run(output_file: Option<&str>, budget: Option<Duration>) {
+    let report = generate(budget);
+    match output_file {
+        Some(output_file) => {
+            fs::write(output_file, report).expect("Something went wrong writing the file");
+            println!("Report written to {}", output_file);
+        }
+        None => print!("{}", report),
+    }
+}
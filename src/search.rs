@@ -0,0 +1,90 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::watchdog;
+
+// https://en.wikipedia.org/wiki/A*_search_algorithm
+// A* finds the cheapest path from start to a goal over any hashable state.
+// - `neighbours` returns the states reachable in one move and their costs.
+// - `h` is the heuristic function. h(n) estimates the cost to reach a goal
+//   from n and must never overestimate it. Use `|_| 0` for Dijkstra.
+//
+// Returns the cost and the states along the path (including start and goal)
+// or `None` if no goal can be reached.
+pub fn a_star<S, G, N, I, H>(start: S, is_goal: G, neighbours: N, h: H) -> Option<(usize, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    G: Fn(&S) -> bool,
+    N: Fn(&S) -> I,
+    I: IntoIterator<Item = (S, usize)>,
+    H: Fn(&S) -> usize,
+{
+    // States are stored once and referred to by index everywhere else.
+    let mut states: Vec<S> = vec![start.clone()];
+    let mut ids: HashMap<S, usize> = HashMap::from([(start.clone(), 0)]);
+
+    // For node n, came_from[n] is the node immediately preceding it on the
+    // cheapest path from start to n currently known.
+    let mut came_from: HashMap<usize, usize> = HashMap::new();
+
+    // For node n, g_score[n] is the cost of the cheapest path from start to n
+    // currently known.
+    let mut g_score: HashMap<usize, usize> = HashMap::from([(0, 0)]);
+
+    // The set of discovered nodes that may need to be (re-)expanded ordered
+    // by f_score[n] := g_score[n] + h(n). Nodes are pushed again when a
+    // cheaper path is found instead of being updated in place, so stale
+    // entries are skipped when popped.
+    let mut open_set = BinaryHeap::new();
+    open_set.push(Reverse((h(&start), 0, 0)));
+
+    while let Some(Reverse((_, g, current))) = open_set.pop() {
+        // Give up once out of time.
+        if watchdog::cancelled() {
+            return None;
+        }
+
+        if g > g_score[&current] {
+            continue;
+        }
+
+        if is_goal(&states[current]) {
+            let mut path = VecDeque::from([states[current].clone()]);
+            let mut curr = current;
+            while let Some(&prev) = came_from.get(&curr) {
+                path.push_front(states[prev].clone());
+                curr = prev;
+            }
+            return Some((g, path.into_iter().collect()));
+        }
+
+        for (neighbour, d) in neighbours(&states[current]) {
+            let tentative_g_score = g + d;
+
+            let id = match ids.get(&neighbour) {
+                Some(&id) => id,
+                None => {
+                    states.push(neighbour.clone());
+                    ids.insert(neighbour.clone(), states.len() - 1);
+                    states.len() - 1
+                }
+            };
+
+            if tentative_g_score < *g_score.get(&id).unwrap_or(&usize::MAX) {
+                // This path to neighbour is better than any previous one.
+                // Record it!
+                came_from.insert(id, current);
+                g_score.insert(id, tentative_g_score);
+                open_set.push(Reverse((
+                    tentative_g_score + h(&neighbour),
+                    tentative_g_score,
+                    id,
+                )));
+            }
+        }
+    }
+
+    // Open set is empty but goal was never reached.
+    None
+}
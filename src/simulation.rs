@@ -0,0 +1,37 @@
+/// A puzzle whose state advances one discrete step at a time.
+pub trait Simulation {
+    type State: Clone;
+
+    /// Advances the simulation by a single step.
+    fn step(&mut self);
+
+    /// Returns the current state.
+    fn state(&self) -> &Self::State;
+
+    /// Replaces the current state with a previously taken snapshot.
+    ///
+    /// * `snapshot` - The state to restore.
+    fn restore(&mut self, snapshot: Self::State);
+
+    /// Returns a copy of the current state that can be restored later.
+    fn snapshot(&self) -> Self::State {
+        self.state().clone()
+    }
+
+    /// Steps the simulation until the predicate holds for the current state
+    /// and returns the number of steps taken. The predicate is checked before
+    /// the first step.
+    ///
+    /// * `predicate` - Returns true when the simulation should stop.
+    fn run_until<P>(&mut self, predicate: P) -> usize
+    where
+        P: Fn(&Self::State) -> bool,
+    {
+        let mut steps = 0;
+        while !predicate(self.state()) {
+            self.step();
+            steps += 1;
+        }
+        steps
+    }
+}
@@ -0,0 +1,224 @@
+use std::time::{Duration, Instant};
+
+use crate::alloc_stats::{self, AllocStats, Checkpoint};
+use crate::watchdog::{self, Outcome};
+
+/// Solves one part of a puzzle for the given input file and returns the answer.
+pub type Part = fn(&str) -> String;
+
+/// A solved day along with the input file used for the final answers.
+pub struct Solution {
+    pub day: u8,
+    pub input_file: &'static str,
+    pub parts: [Part; 2],
+}
+
+/// Time budget for each part unless one is given on the command line.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The answer for one part of a solution and how long it took to compute.
+/// Allocations are only available with the `alloc-stats` feature and only
+/// for parts that finished.
+pub struct PartResult {
+    pub answer: Outcome<String>,
+    pub elapsed: Duration,
+    pub memory: Option<AllocStats>,
+}
+
+pub const SOLUTIONS: &[Solution] = &[
+    #[cfg(feature = "day01")]
+    Solution {
+        day: 1,
+        input_file: "./inputs/day01/1.txt",
+        parts: [crate::day01::part1, crate::day01::part2],
+    },
+    #[cfg(feature = "day02")]
+    Solution {
+        day: 2,
+        input_file: "./inputs/day02/1.txt",
+        parts: [crate::day02::part1, crate::day02::part2],
+    },
+    #[cfg(feature = "day03")]
+    Solution {
+        day: 3,
+        input_file: "./inputs/day03/1.txt",
+        parts: [crate::day03::part1, crate::day03::part2],
+    },
+    #[cfg(feature = "day04")]
+    Solution {
+        day: 4,
+        input_file: "./inputs/day04/1.txt",
+        parts: [crate::day04::part1, crate::day04::part2],
+    },
+    #[cfg(feature = "day05")]
+    Solution {
+        day: 5,
+        input_file: "./inputs/day05/1.txt",
+        parts: [crate::day05::part1, crate::day05::part2],
+    },
+    #[cfg(feature = "day06")]
+    Solution {
+        day: 6,
+        input_file: "./inputs/day06/1.txt",
+        parts: [crate::day06::part1, crate::day06::part2],
+    },
+    #[cfg(feature = "day07")]
+    Solution {
+        day: 7,
+        input_file: "./inputs/day07/1.txt",
+        parts: [crate::day07::part1, crate::day07::part2],
+    },
+    #[cfg(feature = "day08")]
+    Solution {
+        day: 8,
+        input_file: "./inputs/day08/1.txt",
+        parts: [crate::day08::part1, crate::day08::part2],
+    },
+    #[cfg(feature = "day09")]
+    Solution {
+        day: 9,
+        input_file: "./inputs/day09/1.txt",
+        parts: [crate::day09::part1, crate::day09::part2],
+    },
+    #[cfg(feature = "day10")]
+    Solution {
+        day: 10,
+        input_file: "./inputs/day10/1.txt",
+        parts: [crate::day10::part1, crate::day10::part2],
+    },
+    #[cfg(feature = "day11")]
+    Solution {
+        day: 11,
+        input_file: "./inputs/day11/1.txt",
+        parts: [crate::day11::part1, crate::day11::part2],
+    },
+    #[cfg(feature = "day12")]
+    Solution {
+        day: 12,
+        input_file: "./inputs/day12/3.txt",
+        parts: [crate::day12::part1, crate::day12::part2],
+    },
+    #[cfg(feature = "day13")]
+    Solution {
+        day: 13,
+        input_file: "./inputs/day13/1.txt",
+        parts: [crate::day13::part1, crate::day13::part2],
+    },
+    #[cfg(feature = "day14")]
+    Solution {
+        day: 14,
+        input_file: "./inputs/day14/1.txt",
+        parts: [crate::day14::part1, crate::day14::part2],
+    },
+    #[cfg(feature = "day15")]
+    Solution {
+        day: 15,
+        input_file: "./inputs/day15/1.txt",
+        parts: [crate::day15::part1, crate::day15::part2],
+    },
+    #[cfg(feature = "day16")]
+    Solution {
+        day: 16,
+        input_file: "./inputs/day16/1.txt",
+        parts: [crate::day16::part1, crate::day16::part2],
+    },
+    #[cfg(feature = "day17")]
+    Solution {
+        day: 17,
+        input_file: "./inputs/day17/1.txt",
+        parts: [crate::day17::part1, crate::day17::part2],
+    },
+    #[cfg(feature = "day18")]
+    Solution {
+        day: 18,
+        input_file: "./inputs/day18/1.txt",
+        parts: [crate::day18::part1, crate::day18::part2],
+    },
+    #[cfg(feature = "day19")]
+    Solution {
+        day: 19,
+        input_file: "./inputs/day19/1.txt",
+        parts: [crate::day19::part1, crate::day19::part2],
+    },
+    #[cfg(feature = "day20")]
+    Solution {
+        day: 20,
+        input_file: "./inputs/day20/1.txt",
+        parts: [crate::day20::part1, crate::day20::part2],
+    },
+    #[cfg(feature = "day21")]
+    Solution {
+        day: 21,
+        input_file: "./inputs/day21/1.txt",
+        parts: [crate::day21::part1, crate::day21::part2],
+    },
+    #[cfg(feature = "day22")]
+    Solution {
+        day: 22,
+        input_file: "./inputs/day22/1.txt",
+        parts: [crate::day22::part1, crate::day22::part2],
+    },
+    #[cfg(feature = "day23")]
+    Solution {
+        day: 23,
+        input_file: "./inputs/day23/1.txt",
+        parts: [crate::day23::part1, crate::day23::part2],
+    },
+    #[cfg(feature = "day24")]
+    Solution {
+        day: 24,
+        input_file: "./inputs/day24/1.txt",
+        parts: [crate::day24::part1, crate::day24::part2],
+    },
+    #[cfg(feature = "day25")]
+    Solution {
+        day: 25,
+        input_file: "./inputs/day25/1.txt",
+        parts: [crate::day25::part1, crate::day25::part2],
+    },
+];
+
+/// Returns the solution for a day if it has been solved.
+///
+/// * `day` - The day.
+pub fn find(day: u8) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|s| s.day == day)
+}
+
+/// Runs one part of a solution and measures how long it takes. The part is
+/// cancelled if it runs past the time budget.
+///
+/// * `solution`   - The solution.
+/// * `i`          - Index of the part to run.
+/// * `input_file` - The input file.
+/// * `budget`     - Time budget or `None` to wait indefinitely.
+pub fn run_part(
+    solution: &Solution,
+    i: usize,
+    input_file: &str,
+    budget: Option<Duration>,
+) -> PartResult {
+    let part = solution.parts[i];
+    let input_file = input_file.to_string();
+    let name = format!("day {:02} part {}", solution.day, i + 1);
+
+    let start = Instant::now();
+    let outcome = watchdog::run(name, budget, move || {
+        // Count allocations on the worker thread only.
+        let checkpoint = Checkpoint::new();
+        let answer = part(&input_file);
+        (answer, checkpoint.stats())
+    });
+    let elapsed = start.elapsed();
+
+    let (answer, memory) = match outcome {
+        Outcome::Done((answer, memory)) => (Outcome::Done(answer), Some(memory)),
+        Outcome::Timeout => (Outcome::Timeout, None),
+        Outcome::Panicked => (Outcome::Panicked, None),
+    };
+    PartResult {
+        answer,
+        elapsed,
+        memory: memory.filter(|_| alloc_stats::enabled()),
+    }
+}
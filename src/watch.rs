@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use aoc_2021::{alloc_stats, solutions};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Returns the last modification time of a file or `None` if it can't be read.
+///
+/// * `path` - The file path.
+fn modified(path: &str) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Where the rebuilt days go. Sharing the watcher's target directory would
+/// relink the running executable, which Windows doesn't allow.
+const TARGET_DIR: &str = "target/watch";
+
+/// Cargo features this executable was built with. Only enabled days are
+/// registered in `solutions::SOLUTIONS`, and each day has a feature named
+/// after it.
+fn features() -> Vec<String> {
+    let mut features: Vec<String> = solutions::SOLUTIONS
+        .iter()
+        .map(|solution| format!("day{:02}", solution.day))
+        .collect();
+    if alloc_stats::enabled() {
+        features.push("alloc-stats".to_string());
+    }
+    features
+}
+
+/// Returns the `cargo run` flags that rebuild with the same profile and
+/// features as this executable into a separate target directory.
+fn build_flags() -> Vec<String> {
+    let mut flags = vec![
+        "--quiet".to_string(),
+        "--target-dir".to_string(),
+        TARGET_DIR.to_string(),
+    ];
+    if !cfg!(debug_assertions) {
+        flags.push("--release".to_string());
+    }
+
+    let features = features();
+    flags.push("--no-default-features".to_string());
+    if !features.is_empty() {
+        flags.push("--features".to_string());
+        flags.push(features.join(","));
+    }
+    flags
+}
+
+/// Runs a day through `cargo run` so that source changes are rebuilt and
+/// returns the output lines or `None` if the build or run failed. Build errors
+/// are shown as they happen.
+///
+/// * `day`        - The day.
+/// * `input_file` - The input file.
+/// * `budget`     - Time limit for each part or `None` to wait indefinitely.
+fn run_day(day: u8, input_file: &str, budget: Option<Duration>) -> Option<Vec<String>> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let timeout = budget.map_or(0, |b| b.as_secs()).to_string();
+
+    let output = Command::new(cargo)
+        .arg("run")
+        .args(build_flags())
+        .args(["--", &day.to_string(), input_file])
+        .args(["--timeout", &timeout])
+        .stderr(Stdio::inherit())
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Some(stdout.lines().map(String::from).collect())
+}
+
+/// Returns the source files a day's answers depend on: the day itself, the
+/// driver that runs it and every shared module either of them uses.
+///
+/// * `day` - The day.
+fn sources(day: u8) -> Vec<String> {
+    let mut sources = vec![
+        format!("./src/day{:02}.rs", day),
+        "./src/solutions.rs".to_string(),
+    ];
+
+    // Follow `crate::<module>` paths. Days never use each other.
+    let mut i = 0;
+    while i < sources.len() {
+        let contents = fs::read_to_string(&sources[i]).unwrap_or_default();
+        for (j, _) in contents.match_indices("crate::") {
+            let module: String = contents[j + "crate::".len()..]
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+                .collect();
+            let path = format!("./src/{}.rs", module);
+            if !module.starts_with("day") && !sources.contains(&path) && modified(&path).is_some() {
+                sources.push(path);
+            }
+        }
+        i += 1;
+    }
+    sources
+}
+
+/// Prints the output lines and marks the ones that changed since last time.
+///
+/// * `lines`    - The new output.
+/// * `previous` - The output from the previous run if any.
+fn show_diff(lines: &[String], previous: Option<&Vec<String>>) {
+    for (i, line) in lines.iter().enumerate() {
+        match previous.and_then(|p| p.get(i)) {
+            Some(old) if old != line => {
+                // Only repeat the old answer, not the label.
+                let answer = old.split_once(" = ").map_or(old.trim(), |(_, a)| a);
+                println!("~ {}  (was {})", line, answer)
+            }
+            Some(_) => println!("  {}", line),
+            None if previous.is_some() => println!("+ {}", line),
+            None => println!("  {}", line),
+        }
+    }
+    if let Some(previous) = previous {
+        for old in previous.iter().skip(lines.len()) {
+            println!("- {}", old);
+        }
+    }
+}
+
+/// Polls the source and input files for a day and re-runs the example and
+/// real inputs whenever any of them change. Runs with the same profile and
+/// features as this executable. Runs until interrupted.
+///
+/// * `day`    - The day.
+/// * `budget` - Time limit for each part or `None` to wait indefinitely.
+pub fn run(day: u8, budget: Option<Duration>) {
+    let example = format!("./inputs/day{:02}/0.txt", day);
+    let real = match solutions::find(day) {
+        Some(solution) => solution.input_file.to_string(),
+        None => format!("./inputs/day{:02}/1.txt", day),
+    };
+    let inputs = [("example", example.as_str()), ("input", real.as_str())];
+
+    // Edits can change which shared modules the day uses.
+    let watch_list = || {
+        let mut watched = sources(day);
+        watched.push(example.clone());
+        watched.push(real.clone());
+        watched
+    };
+    let mut watched = watch_list();
+
+    println!("watching {} (Ctrl-C to stop)", watched.join(", "));
+
+    let mut times: HashMap<String, Option<SystemTime>> = HashMap::new();
+    let mut answers: HashMap<&str, Vec<String>> = HashMap::new();
+    loop {
+        let changed: Vec<String> = watched
+            .iter()
+            .filter(|&path| times.get(path) != Some(&modified(path)))
+            .cloned()
+            .collect();
+
+        if !changed.is_empty() {
+            for path in changed.iter() {
+                times.insert(path.clone(), modified(path));
+            }
+            println!("\nchanged: {}", changed.join(", "));
+
+            for (name, input_file) in inputs {
+                if modified(input_file).is_none() {
+                    println!("{}: {} not found", name, input_file);
+                    continue;
+                }
+
+                println!("{}: {}", name, input_file);
+                match run_day(day, input_file, budget) {
+                    Some(lines) => {
+                        show_diff(&lines, answers.get(input_file));
+                        answers.insert(input_file, lines);
+                    }
+                    None => println!("  failed"),
+                }
+            }
+
+            let previous = std::mem::replace(&mut watched, watch_list());
+            for path in watched.iter() {
+                // Already up to date with the run that just finished.
+                times.entry(path.clone()).or_insert_with(|| modified(path));
+            }
+            if watched != previous {
+                println!("\nwatching {}", watched.join(", "));
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
@@ -0,0 +1,84 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+thread_local! {
+    // Cancellation flag for the work running on the current thread.
+    static CANCEL: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+}
+
+/// Returns true if the work running on the current thread has run out of time.
+/// Long running solvers should check this periodically and bail out early.
+pub fn cancelled() -> bool {
+    CANCEL.with(|cancel| {
+        cancel
+            .borrow()
+            .as_ref()
+            .is_some_and(|c| c.load(Ordering::Relaxed))
+    })
+}
+
+/// The result of running some work under a time budget.
+pub enum Outcome<T> {
+    Done(T),
+    Timeout,
+    Panicked,
+}
+
+impl<T: fmt::Display> fmt::Display for Outcome<T> {
+    /// Formats the value using the given formatter.
+    ///
+    /// * `f`     - Formatter
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Done(v) => write!(f, "{}", v),
+            Self::Timeout => write!(f, "TIMEOUT"),
+            Self::Panicked => write!(f, "PANIC"),
+        }
+    }
+}
+
+/// Runs work on a separate thread and waits up to `budget` for it to finish.
+/// When the budget runs out the work is signalled to cancel and left to wind
+/// down in the background.
+///
+/// * `name`   - Name of the worker thread.
+/// * `budget` - Time budget or `None` to wait indefinitely.
+/// * `work`   - The work to run.
+pub fn run<T, F>(name: String, budget: Option<Duration>, work: F) -> Outcome<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let cancel = Arc::new(AtomicBool::new(false));
+    let token = cancel.clone();
+    let (tx, rx) = mpsc::channel();
+
+    thread::Builder::new()
+        .name(name)
+        .spawn(move || {
+            CANCEL.with(|cancel| *cancel.borrow_mut() = Some(token));
+            // Receiver is gone if we ran out of time.
+            let _ = tx.send(work());
+        })
+        .expect("Unable to spawn worker thread");
+
+    let result = match budget {
+        Some(budget) => rx.recv_timeout(budget).map_err(|e| match e {
+            mpsc::RecvTimeoutError::Timeout => Outcome::Timeout,
+            mpsc::RecvTimeoutError::Disconnected => Outcome::Panicked,
+        }),
+        None => rx.recv().map_err(|_| Outcome::Panicked),
+    };
+
+    match result {
+        Ok(v) => Outcome::Done(v),
+        Err(outcome) => {
+            cancel.store(true, Ordering::Relaxed);
+            outcome
+        }
+    }
+}